@@ -0,0 +1,39 @@
+// Criterion benchmarks of parsing and each part of every day. Each day uses its input
+// from the usual input resolution (see README), falling back to the puzzle example.
+
+// External crates
+use criterion::{Criterion, criterion_group, criterion_main};
+
+// Crate modules
+use advent_22::general::{Input, InputResolver};
+use advent_22::{Part, registry};
+
+fn example_input(day: u8) -> String {
+    let path = format!("{}/tests/fixtures/day_{}_example.txt", env!("CARGO_MANIFEST_DIR"), day);
+    std::fs::read_to_string(path).expect("example fixture")
+}
+
+fn solvers(c: &mut Criterion) {
+
+    let resolver = InputResolver::new(None, None).expect("input resolver");
+
+    for solver in registry() {
+
+        let day = solver.day();
+
+        let input = resolver.read(day).unwrap_or_else(|_| example_input(day));
+
+        let parsed = solver.parse(Input::from(input.as_str())).expect("parsing input");
+
+        let mut group = c.benchmark_group(format!("day_{}", day));
+
+        group.bench_function("parse", |b| b.iter(|| solver.parse(Input::from(input.as_str()))));
+        group.bench_function("part_1", |b| b.iter(|| solver.solve_part(parsed.as_ref(), Part::One)));
+        group.bench_function("part_2", |b| b.iter(|| solver.solve_part(parsed.as_ref(), Part::Two)));
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
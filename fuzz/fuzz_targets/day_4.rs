@@ -0,0 +1,17 @@
+#![no_main]
+
+// External crates
+use libfuzzer_sys::fuzz_target;
+
+// Crate modules
+use advent_22::general::Input;
+use advent_22::{Part, find_solver};
+
+// Any input may be an error, but none may panic
+fuzz_target!(|input: &str| {
+
+    let solver = find_solver(4).unwrap();
+
+    let _ = solver.run(Input::from(input), &Part::ALL);
+    let _ = solver.validate(Input::from(input));
+});
@@ -0,0 +1,215 @@
+// Standard library
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// External crates
+use anyhow::{Context, Result};
+
+// Crate modules
+use crate::general::*;
+use crate::solution::{Answer, Part};
+
+// Default answers file, looked up in the working directory
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+// Known answers, keyed by day, then input name, then part:
+//
+// [day_5.day_5]
+// part_1 = "CMZ"
+// part_2 = "MCD"
+#[derive(Debug, Default)]
+pub struct AnswerStore {
+    path: PathBuf,
+    answers: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+impl AnswerStore {
+
+    // Load an answers file; a missing file is an empty store
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+
+        let path = path.as_ref();
+
+        let answers = if path.try_exists().context("loading answers file")? {
+
+            let answers_string = read_text_input(path)
+            .context("loading answers file")?;
+
+            toml::from_str(&answers_string)
+            .with_context(|| format!("parsing answers file '{}'", path.display()))?
+
+        } else {
+
+            BTreeMap::new()
+        };
+
+        Ok(
+            Self {
+                path: path.to_path_buf(),
+                answers
+            }
+        )
+    }
+
+    pub fn save(&self) -> Result<()> {
+
+        let answers_string = toml::to_string(&self.answers)
+        .context("saving answers file")?;
+
+        fs::write(&self.path, answers_string)
+        .with_context(|| format!("writing answers file '{}'", self.path.display()))
+    }
+
+    pub fn get(&self, day: u8, input_name: &str, part: Part) -> Option<&str> {
+        self.answers
+        .get(&day_key(day))?
+        .get(input_name)?
+        .get(&part_key(part))
+        .map(|answer| answer.as_str())
+    }
+
+    pub fn record(&mut self, day: u8, input_name: &str, part: Part, answer: &str) {
+        self.answers
+        .entry(day_key(day))
+        .or_default()
+        .entry(input_name.to_string())
+        .or_default()
+        .insert(part_key(part), answer.to_string());
+    }
+
+    // Compare a solver's result against the recorded answer
+    pub fn check(&self, day: u8, input_name: &str, part: Part, result: Result<Answer>) -> Outcome {
+
+        let answer = match result {
+            Result::Ok(answer) => answer,
+            Result::Err(error) => return Outcome::Failed(error),
+        };
+
+        match self.get(day, input_name, part) {
+            Some(expected) if expected == answer.to_string() => Outcome::Match(answer),
+            Some(expected) => Outcome::Mismatch { expected: expected.to_string(), actual: answer },
+            None => Outcome::Unrecorded(answer),
+        }
+    }
+}
+
+fn day_key(day: u8) -> String {
+    format!("day_{}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part_{}", part)
+}
+
+// Name of an input in the answers file: its file name without extension, nor that of its
+// compression, so 'day_4.txt.gz' has the answers of 'day_4.txt'
+pub fn input_name<P: AsRef<Path>>(path: P) -> String {
+
+    let path = path.as_ref();
+
+    let uncompressed = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz" | "zst") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    };
+
+    uncompressed
+    .file_stem()
+    .map(|stem| stem.to_string_lossy().to_string())
+    .unwrap_or_else(|| path.display().to_string())
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    Match(Answer),
+    Mismatch { expected: String, actual: Answer },
+    Unrecorded(Answer),
+    Failed(anyhow::Error),
+}
+
+// Show the expected and actual answers one above the other, marking the differing
+// characters with a caret
+pub fn diff_answers(expected: &str, actual: &str) -> String {
+    diff_labelled(("expected", expected), ("actual", actual))
+}
+
+// Same as `diff_answers`, between the answers of two runs
+pub fn diff_runs(previous: &str, current: &str) -> String {
+    diff_labelled(("previous", previous), ("current", current))
+}
+
+fn diff_labelled((before_label, before): (&str, &str), (after_label, after): (&str, &str)) -> String {
+
+    let expected_chars: Vec<char> = before.chars().collect();
+    let actual_chars: Vec<char> = after.chars().collect();
+
+    let mut markers = String::new();
+
+    for index in 0..expected_chars.len().max(actual_chars.len()) {
+
+        if expected_chars.get(index) == actual_chars.get(index) {
+            markers.push(' ');
+        } else {
+            markers.push('^');
+        }
+    }
+
+    let width = before_label.len().max(after_label.len());
+
+    format!(
+        "{:>width$}: {}\n{:>width$}: {}\n{}{}",
+        before_label,
+        before,
+        after_label,
+        after,
+        " ".repeat(width + 2),
+        markers.trim_end(),
+        width = width
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffs_mark_differing_characters() {
+        assert_eq!(diff_answers("CMZ", "CMD"), "expected: CMZ\n  actual: CMD\n            ^");
+        assert_eq!(diff_runs("157", "1570"), "previous: 157\n current: 1570\n             ^");
+    }
+
+    #[test]
+    fn input_names_skip_compression() {
+        assert_eq!(input_name("inputs/day_4.txt"), "day_4");
+        assert_eq!(input_name("inputs/day_4.txt.gz"), "day_4");
+        assert_eq!(input_name("inputs/day_4.txt.zst"), "day_4");
+        assert_eq!(input_name("variant.gz"), "variant");
+    }
+
+    #[cfg(feature = "compressed")]
+    #[test]
+    fn compressed_inputs_keep_their_answers() {
+
+        use std::io::Write;
+        use crate::solution::find_solver;
+        use crate::test_util::TempDir;
+
+        let dir = TempDir::new("answers");
+
+        let mut store = AnswerStore::load(dir.join(ANSWERS_FILE)).unwrap();
+        store.record(4, "day_4", Part::One, "2");
+
+        // The recorded input, compressed after its answer was recorded
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(include_bytes!("../tests/fixtures/day_4_example.txt")).unwrap();
+        fs::write(dir.join("day_4.txt.gz"), gzip.finish().unwrap()).unwrap();
+
+        let resolver = InputResolver::new(None, Some(dir.to_path_buf())).unwrap();
+        let input_path = resolver.resolve(4);
+
+        let mut results = find_solver(4).unwrap().run(resolver.input(4), &[Part::One]).unwrap();
+
+        assert_eq!(input_path, dir.join("day_4.txt.gz"));
+        assert!(matches!(store.check(4, &input_name(&input_path), Part::One, results.remove(0)), Outcome::Match(_)));
+    }
+}
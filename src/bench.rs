@@ -0,0 +1,144 @@
+// Standard library
+use std::time::{Duration, Instant};
+
+// External crates
+use anyhow::{Result, anyhow};
+
+// Crate modules
+use crate::general::Input;
+use crate::solution::{Part, Solver};
+
+// Durations of repeated runs of the same step
+#[derive(Debug, Clone, Default)]
+pub struct Samples {
+    durations: Vec<Duration>,
+}
+
+impl Samples {
+
+    pub fn push(&mut self, duration: Duration) {
+        self.durations.push(duration);
+    }
+
+    pub fn len(&self) -> usize {
+        self.durations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.durations.is_empty()
+    }
+
+    pub fn min(&self) -> Duration {
+        self.durations.iter().min().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.durations.iter().max().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+
+        let mut sorted = self.durations.clone();
+        sorted.sort_unstable();
+
+        match sorted.len() {
+            0 => Duration::ZERO,
+            len if len % 2 == 1 => sorted[len / 2],
+            len => (sorted[len / 2 - 1] + sorted[len / 2]) / 2,
+        }
+    }
+}
+
+// Timings of parsing and of each part of a day
+#[derive(Debug, Clone)]
+pub struct DayTimings {
+    pub day: u8,
+    pub parse: Samples,
+    pub part_one: Samples,
+    pub part_two: Samples,
+}
+
+// Run a day `runs` times, timing parsing and each part separately
+pub fn bench_solver(solver: &dyn Solver, input: &str, runs: usize) -> Result<DayTimings> {
+
+    if runs == 0 {
+        return Err(anyhow!("Benchmarks need at least one run"))
+    }
+
+    let mut timings = DayTimings {
+        day: solver.day(),
+        parse: Samples::default(),
+        part_one: Samples::default(),
+        part_two: Samples::default(),
+    };
+
+    for _ in 0..runs {
+
+        let start = Instant::now();
+        let parsed = solver.parse(Input::from(input))?;
+        timings.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve_part(parsed.as_ref(), Part::One)?;
+        timings.part_one.push(start.elapsed());
+
+        let start = Instant::now();
+        solver.solve_part(parsed.as_ref(), Part::Two)?;
+        timings.part_two.push(start.elapsed());
+    }
+
+    Ok(timings)
+}
+
+// Render the timings of several days as a table of min / median / max per step
+pub fn format_table(all_timings: &[DayTimings]) -> String {
+
+    let mut table = format!(
+        "{:<5} {:<8} {:>12} {:>12} {:>12}\n",
+        "Day", "Step", "Min", "Median", "Max"
+    );
+
+    for timings in all_timings {
+
+        let steps = [
+            ("parse", &timings.parse),
+            ("part 1", &timings.part_one),
+            ("part 2", &timings.part_two),
+        ];
+
+        for (step, samples) in steps {
+            table.push_str(&format!(
+                "{:<5} {:<8} {:>12} {:>12} {:>12}\n",
+                timings.day,
+                step,
+                format!("{:.2?}", samples.min()),
+                format!("{:.2?}", samples.median()),
+                format!("{:.2?}", samples.max()),
+            ));
+        }
+    }
+
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(millis: &[u64]) -> Samples {
+        Samples { durations: millis.iter().map(|millis| Duration::from_millis(*millis)).collect() }
+    }
+
+    #[test]
+    fn min_median_max() {
+
+        let odd = samples(&[5, 1, 3]);
+
+        assert_eq!(odd.min(), Duration::from_millis(1));
+        assert_eq!(odd.median(), Duration::from_millis(3));
+        assert_eq!(odd.max(), Duration::from_millis(5));
+
+        assert_eq!(samples(&[4, 1, 2, 3]).median(), Duration::from_micros(2500));
+        assert_eq!(samples(&[]).median(), Duration::ZERO);
+    }
+}
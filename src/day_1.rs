@@ -1,10 +1,14 @@
 // Standard library
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use tracing::warn;
 
 // Crate modules
+use crate::error::{AdventError, LineContext, ParseMode, Span, parse_lines};
 use crate::general::*;
+use crate::params::Params;
+use crate::solution::Solution;
 
 // Function 1:
 /* 
@@ -22,10 +26,10 @@ they'd like to know how many Calories are being carried by the Elf carrying the
 In the example above, this is 24000 (carried by the fourth Elf).
 
 Find the Elf carrying the most Calories. How many total Calories is that Elf carrying? */
-pub fn get_elf_calories() -> Result<i32> {
+pub fn get_elf_calories(input: impl Into<Input>) -> Result<i32> {
 
-    // Read the input file
-    let input_string = read_text_input("/home/acente/Advent_inputs/day_1_input_1.txt")
+    // Read the input, line by line
+    let lines = LineReader::new(input.into())
     .context("getting Calories from top-carrying Elf")?;
 
     // Create Elf Vector
@@ -34,10 +38,18 @@ pub fn get_elf_calories() -> Result<i32> {
     // Initialize a Calorie counter
     let mut calorie_counter = 0;
 
+    // Whether the last line read was empty
+    let mut last_line_empty = true;
+
     // Read each line
-    for line in input_string.lines() {
-        
-        if line.is_empty() {
+    for line in lines {
+
+        let line = line?;
+        let text = line.text();
+
+        last_line_empty = text.is_empty();
+
+        if text.is_empty() {
 
             // Push as the total Calorie count of an Elf, reset counter, continue
             elf_calories.push(calorie_counter);
@@ -46,16 +58,15 @@ pub fn get_elf_calories() -> Result<i32> {
         } else {
 
             // Try to convert to i32
-            match line.parse::<i32>() {
+            match text.parse::<i32>() {
 
                 // If succeeded, add to the Calorie counter
-                Result::Ok(num) => calorie_counter += num,
+                Result::Ok(num) => calorie_counter = add_calories(calorie_counter, num, &line)?,
 
-                // Otherwise, show me what went wrong, push as total Calorie count of an Elf, reset counter, continue
+                // Otherwise, warn about what went wrong, push as total Calorie count of an Elf, reset counter, continue
                 Result::Err(error) => {
 
-                    println!("Parsing error: {:?}", error);
-                    println!("Line: {}", line);
+                    warn!(line = line.number(), text, "Parsing error, treating the line as a separator: {}", error);
                     elf_calories.push(calorie_counter);
                     calorie_counter = 0;
 
@@ -64,10 +75,15 @@ pub fn get_elf_calories() -> Result<i32> {
         }
     };
 
+    // Push the last Elf, if the list doesn't end with an empty line
+    if !last_line_empty {
+        elf_calories.push(calorie_counter);
+    }
+
 
     // Give me the Calorie count of the Elf with the most Calories
     let Some(max_calories) = elf_calories.iter().max() else {
-        return Err(anyhow!("Couldn't find a maximum in the Elf vector, since it was empty."))
+        return Err(AdventError::validation("Couldn't find a maximum in the Elf vector, since it was empty.", None).into())
     };
 
     // Return the result
@@ -91,25 +107,29 @@ carried by these three elves is 45000.
 Find the top three Elves carrying the most Calories. How many Calories are those Elves carrying in 
 total?
  */
-fn get_elf_calories_vector() -> Result<Vec<i32>> {
+fn get_elf_calories_vector(lines: impl Iterator<Item = Result<Line>>) -> Result<Vec<i32>> {
 
     // So now I see how this goes, I will start re-factoring instead. This function just gives me 
     // the Elf vector, and I will write other functions for working with that.
 
-    // Read the input file
-    let input_string = read_text_input("/home/acente/Advent_inputs/day_1_input_1.txt")
-    .context("getting Calories from top-carrying Elf")?;
-
     // Create Elf Vector
     let mut elf_calories: Vec<i32> = Vec::new();
 
     // Initialize a Calorie counter
     let mut calorie_counter = 0;
 
+    // Whether the last line read was empty
+    let mut last_line_empty = true;
+
     // Read each line
-    for line in input_string.lines() {
-        
-        if line.is_empty() {
+    for line in lines {
+
+        let line = line?;
+        let text = line.text();
+
+        last_line_empty = text.is_empty();
+
+        if text.is_empty() {
 
             // Push as the total Calorie count of an Elf, reset counter, continue
             elf_calories.push(calorie_counter);
@@ -118,16 +138,15 @@ fn get_elf_calories_vector() -> Result<Vec<i32>> {
         } else {
 
             // Try to convert to i32
-            match line.parse::<i32>() {
+            match text.parse::<i32>() {
 
                 // If succeeded, add to the Calorie counter
-                Result::Ok(num) => calorie_counter += num,
+                Result::Ok(num) => calorie_counter = add_calories(calorie_counter, num, &line)?,
 
-                // Otherwise, show me what went wrong, push as total Calorie count of an Elf, reset counter, continue
+                // Otherwise, warn about what went wrong, push as total Calorie count of an Elf, reset counter, continue
                 Result::Err(error) => {
 
-                    println!("Parsing error: {:?}", error);
-                    println!("Line: {}", line);
+                    warn!(line = line.number(), text, "Parsing error, treating the line as a separator: {}", error);
                     elf_calories.push(calorie_counter);
                     calorie_counter = 0;
 
@@ -136,9 +155,24 @@ fn get_elf_calories_vector() -> Result<Vec<i32>> {
         }
     };
 
+    // Push the last Elf, if the list doesn't end with an empty line
+    if !last_line_empty {
+        elf_calories.push(calorie_counter);
+    }
+
     Ok(elf_calories)
 }
 
+// Add the Calories of an item to those of its Elf
+fn add_calories(calorie_counter: i32, calories: i32, line: &Line) -> Result<i32> {
+
+    let Some(total) = calorie_counter.checked_add(calories) else {
+        return Err(AdventError::validation("The Calories of this Elf overflow a 32-bit integer", Some(Span::whole(line.text()))).into())
+    };
+
+    Ok(total).on_line(line)
+}
+
 fn sort_elf_calories_vec(elf_calories_vec: &mut [i32], high_to_low: bool) {
     
     // Sort the vector, low to high
@@ -150,31 +184,153 @@ fn sort_elf_calories_vec(elf_calories_vec: &mut [i32], high_to_low: bool) {
 
 }
 
-fn get_total_count(elf_calories_vec: &[i32], n_first: usize) -> i32 {
-
-    //Check size
-    if n_first >= elf_calories_vec.len() {
+fn get_total_count(elf_calories_vec: &[i32], n_first: usize) -> Result<i32> {
 
-        // If n_size is larger than the number of elves, return total sum
-        elf_calories_vec.iter().sum()
+    // If n_size is larger than the number of elves, this is the total sum
+    let top_elves = &elf_calories_vec[0..n_first.min(elf_calories_vec.len())];
 
-    } else {
-
-        // Otherwise return the total count of the n_first first elements
-        elf_calories_vec[0..n_first].iter().sum()
+    let Some(total) = top_elves.iter().try_fold(0i32, |total, calories| total.checked_add(*calories)) else {
+        return Err(AdventError::simulation(format!("The Calories of the top {} Elves overflow a 32-bit integer", n_first)).into())
+    };
 
-    }
+    Ok(total)
 }
 
-pub fn get_top_n_elf_calories(n_first: usize) -> Result<i32> {
+pub fn get_top_n_elf_calories(input: impl Into<Input>, n_first: usize) -> Result<i32> {
+
+    // Read the input, line by line
+    let lines = LineReader::new(input.into())
+    .context("getting top elf calories")?;
 
     // Get the vector
-    let mut elf_calories_vec = get_elf_calories_vector()
+    let mut elf_calories_vec = get_elf_calories_vector(lines)
     .context("getting top elf calories")?;
 
     // Sort it, high to low
     sort_elf_calories_vec(&mut elf_calories_vec, true);
 
     // Get n_first sum
-    Ok(get_total_count(&elf_calories_vec, n_first))
-}
\ No newline at end of file
+    get_total_count(&elf_calories_vec, n_first)
+}
+
+pub struct Day1 {
+    // Number of top-carrying Elves counted in part 2
+    top: usize,
+}
+
+impl Default for Day1 {
+    fn default() -> Self {
+        Self { top: 3 }
+    }
+}
+
+impl Solution for Day1 {
+    type Parsed = Vec<i32>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+    const PARAMS: &'static [&'static str] = &["top"];
+
+    fn configure(&mut self, params: &Params) -> Result<()> {
+
+        self.top = params.usize("top", self.top)?;
+
+        Ok(())
+    }
+
+    fn parse(&self, input: Input) -> Result<Vec<i32>> {
+        get_elf_calories_vector(LineReader::new(input)?)
+    }
+
+    fn part_one(&self, elf_calories: &Vec<i32>) -> Result<i32> {
+
+        let Some(max_calories) = elf_calories.iter().max() else {
+            return Err(AdventError::validation("Couldn't find a maximum in the Elf vector, since it was empty.", None).into())
+        };
+
+        Ok(*max_calories)
+    }
+
+    fn part_two(&self, elf_calories: &Vec<i32>) -> Result<i32> {
+
+        let mut elf_calories_vec = elf_calories.clone();
+
+        sort_elf_calories_vec(&mut elf_calories_vec, true);
+
+        get_total_count(&elf_calories_vec, self.top)
+    }
+
+    // Solving treats a line that is not a number as a separator, with a warning; validating
+    // reports each of them, as well as the Elves whose Calories overflow
+    fn validate(&self, input: Input) -> Result<()> {
+
+        let mut calorie_counter = 0;
+
+        parse_lines(LineReader::new(input)?, ParseMode::CollectAll, |line| {
+
+            let text = line.text();
+
+            if text.is_empty() {
+                calorie_counter = 0;
+                return Ok(None)
+            }
+
+            let Result::Ok(calories) = text.parse::<i32>() else {
+                calorie_counter = 0;
+                return Err(AdventError::parse("A food item is not a number of Calories", Span::whole(text)).into())
+            };
+
+            calorie_counter = add_calories(calorie_counter, calories, line)?;
+
+            Ok(Some(()))
+        })
+        .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+
+    const EXAMPLE: &str = include_str!("../tests/fixtures/day_1_example.txt");
+
+    #[test]
+    fn elf_calories_vector_from_example() {
+        assert_eq!(get_elf_calories_vector(LineReader::new(EXAMPLE.into()).unwrap()).unwrap(), vec![6000, 4000, 11000, 24000, 10000]);
+    }
+
+    #[test]
+    fn total_count_of_more_elves_than_available() {
+
+        let mut elf_calories_vec = get_elf_calories_vector(LineReader::new(EXAMPLE.into()).unwrap()).unwrap();
+
+        sort_elf_calories_vec(&mut elf_calories_vec, true);
+
+        assert_eq!(get_total_count(&elf_calories_vec, 3).unwrap(), 45000);
+        assert_eq!(get_total_count(&elf_calories_vec, 10).unwrap(), 55000);
+    }
+
+    #[test]
+    fn overflowing_calories_are_errors() {
+
+        assert!(get_elf_calories("2147483647\n1\n").is_err());
+        assert!(get_top_n_elf_calories("2147483647\n\n1\n", 3).is_err());
+    }
+
+    #[test]
+    fn non_numeric_lines_fail_validation() {
+
+        let errors = Solver::validate(&Day1::default(), "1000\nabc\n2000\n\nx1\n".into());
+
+        let lines: Vec<Option<usize>> = errors
+        .iter()
+        .map(|error| error.downcast_ref::<AdventError>().and_then(|error| error.span()).and_then(|span| span.line()))
+        .collect();
+
+        assert_eq!(lines, vec![Some(2), Some(5)]);
+        assert!(Solver::validate(&Day1::default(), EXAMPLE.into()).is_empty());
+    }
+}
@@ -3,9 +3,12 @@
 
 // External crates
 use anyhow::{Context, Result, anyhow};
+use serde::{Deserialize, Serialize};
 
 // Crate modules
+use crate::error::{AdventError, ParseMode, Span, parse_lines};
 use crate::general::*;
+use crate::solution::Solution;
 
 // Function 1
 // As I learned in day 1, I should really do small functions in order to reuse them
@@ -61,7 +64,7 @@ In this example, if you were to follow the strategy guide, you would get a total
 What would your total score be if everything goes exactly according to your strategy guide?
  */
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum RPSMove {
     Rock,
     Paper,
@@ -71,7 +74,7 @@ pub enum RPSMove {
 
 impl RPSMove {
 
-    fn decrypt_from_char(encrypted_move: char) -> Result<Self> {
+    pub fn decrypt_from_char(encrypted_move: char) -> Result<Self> {
         
         match encrypted_move {
         
@@ -85,7 +88,7 @@ impl RPSMove {
         }
     }
 
-    fn as_score(&self) -> i32 {
+    pub fn as_score(&self) -> i32 {
         
         match self {
             
@@ -97,7 +100,7 @@ impl RPSMove {
         }
     }
 
-    fn play(&self, opponent_move: Self) -> RPSResult {
+    pub fn play(&self, opponent_move: Self) -> RPSResult {
         match self {
 
             RPSMove::Rock => {
@@ -128,6 +131,7 @@ impl RPSMove {
 }
 
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RPSResult {
     SelfWin(i32),
     SelfLoss(i32),
@@ -135,7 +139,7 @@ pub enum RPSResult {
 }
 
 impl RPSResult {
-    fn get_score(&self) -> i32 {
+    pub fn get_score(&self) -> i32 {
         match self {
             Self::SelfWin(num) | 
             Self::SelfLoss(num) | 
@@ -154,29 +158,29 @@ pub struct RPSRound {
 impl RPSRound {
 
     // Part 1:
-    fn new(line: &str) -> Result<Self> {
+    pub fn new(line: &str) -> Result<Self> {
         
         // Get the characters
         let mut line_chars = line.chars();
 
         // Get the opponent's move
         let Some(opponent_char) = line_chars.next() else {
-            return Err(anyhow!("No characters found in round"))
+            return Err(AdventError::parse("No characters found in round", Span::new(line, 1, 1)).into())
         };
 
         let opponent_move = RPSMove::decrypt_from_char(opponent_char)
-        .context("decrypting new round")?;
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 1, 1)))?;
 
         // Skip the space
         line_chars.next();
 
         // Get the self move
         let Some(self_char) = line_chars.next() else {
-            return Err(anyhow!("Only one character found in round"))
+            return Err(AdventError::parse("Only one character found in round", Span::new(line, 3, 1)).into())
         };
 
         let self_move = RPSMove::decrypt_from_char(self_char)
-        .context("decrypting new round")?;
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 3, 1)))?;
 
         // Get the result
         let result = self_move.play(opponent_move);
@@ -188,29 +192,37 @@ impl RPSRound {
         })
     }
 
+    pub fn from_moves(opponent_move: RPSMove, self_move: RPSMove) -> Self {
+        RPSRound {
+            opponent_move,
+            self_move,
+            result: self_move.play(opponent_move)
+        }
+    }
+
     // Part 2:
-    fn new_2(line: &str) -> Result<Self> {
+    pub fn new_2(line: &str) -> Result<Self> {
         // Get the characters
         let mut line_chars = line.chars();
 
         // Get the opponent's move
         let Some(opponent_char) = line_chars.next() else {
-            return Err(anyhow!("No characters found in round"))
+            return Err(AdventError::parse("No characters found in round", Span::new(line, 1, 1)).into())
         };
 
         let opponent_move = RPSMove::decrypt_from_char(opponent_char)
-        .context("decrypting new round (correct key")?;
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 1, 1)))?;
 
         // Skip the space
         line_chars.next();
 
         // Get the desired strategy
         let Some(self_char) = line_chars.next() else {
-            return Err(anyhow!("Only one character found in round"))
+            return Err(AdventError::parse("Only one character found in round", Span::new(line, 3, 1)).into())
         };
 
         let self_strategy = StrategyMove::decrypt_strategy_from_char(self_char)
-        .context("decrypting new round (correct key)")?;
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 3, 1)))?;
 
         let self_move = self_strategy.move_with_strategy(opponent_move);
 
@@ -223,9 +235,22 @@ impl RPSRound {
             result
         })
     }
+
+    pub fn opponent_move(&self) -> RPSMove {
+        self.opponent_move
+    }
+
+    pub fn self_move(&self) -> RPSMove {
+        self.self_move
+    }
+
+    pub fn result(&self) -> RPSResult {
+        self.result
+    }
 }
 
 // Part 2:
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StrategyMove {
     Win,
     Lose,
@@ -235,7 +260,7 @@ pub enum StrategyMove {
 // Part 2:
 impl StrategyMove {
 
-    fn decrypt_strategy_from_char(encrypted_strategy: char) -> Result<StrategyMove> {
+    pub fn decrypt_strategy_from_char(encrypted_strategy: char) -> Result<StrategyMove> {
 
         match encrypted_strategy {
             
@@ -249,7 +274,7 @@ impl StrategyMove {
         }
     }
 
-    fn move_with_strategy(&self, opponent_move: RPSMove) -> RPSMove {
+    pub fn move_with_strategy(&self, opponent_move: RPSMove) -> RPSMove {
 
         match self {
 
@@ -280,71 +305,94 @@ impl StrategyMove {
     }
 }
 
-fn get_encrypted_rps_rounds() -> Result<Vec<String>> {
+// A round of the strategy guide, before knowing what its second column means
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedRound {
+    opponent_move: RPSMove,
+    second_column: char,
+}
+
+impl EncryptedRound {
 
-    // Read the input file
-    let input_string = read_text_input("/home/acente/Advent_inputs/day_2_input_1.txt")
-    .context("getting encrypted strategy rounds for RPS")?;
+    // The second column must make sense with the correct key, so it's X, Y or Z
+    pub fn new(line: &str) -> Result<Self> {
 
-    // Turn lines into vector of Strings
-    let mut input_lines: Vec<String> = Vec::new();
+        let mut line_chars = line.chars();
 
-    for line in input_string.lines() {
-        input_lines.push(line.to_string());
-    }
+        let Some(opponent_char) = line_chars.next() else {
+            return Err(AdventError::parse("No characters found in round", Span::new(line, 1, 1)).into())
+        };
 
-    Ok(input_lines)
+        let opponent_move = RPSMove::decrypt_from_char(opponent_char)
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 1, 1)))?;
 
-}
+        // Skip the space
+        line_chars.next();
 
-// Part 1:
-fn decrypt_rps_rounds(input_lines: &Vec<String>) -> Result<Vec<RPSRound>> {
+        let Some(second_column) = line_chars.next() else {
+            return Err(AdventError::parse("Only one character found in round", Span::new(line, 3, 1)).into())
+        };
 
-    let mut decrypted_rounds: Vec<RPSRound> = Vec::new();
+        StrategyMove::decrypt_strategy_from_char(second_column)
+        .map_err(|error| AdventError::parse(error.to_string(), Span::new(line, 3, 1)))?;
 
-    for line in input_lines {
-        decrypted_rounds.push(RPSRound::new(line).context("decrypting RPS rounds")?);
+        Ok(EncryptedRound { opponent_move, second_column })
     }
 
-    Ok(decrypted_rounds)
-}
+    // Part 1: the second column is our move
+    pub fn decrypt(&self) -> RPSRound {
 
-// Part 2:
-fn decrypt_rps_rounds_2(input_lines: &Vec<String>) -> Result<Vec<RPSRound>> {
+        let self_move = match self.second_column {
+            'X' => RPSMove::Rock,
+            'Y' => RPSMove::Paper,
+            _ => RPSMove::Scissors,
+        };
+
+        RPSRound::from_moves(self.opponent_move, self_move)
+    }
+
+    // Part 2: the second column is how the round must end
+    pub fn decrypt_2(&self) -> RPSRound {
 
-    let mut decrypted_rounds: Vec<RPSRound> = Vec::new();
+        let strategy = match self.second_column {
+            'X' => StrategyMove::Lose,
+            'Y' => StrategyMove::Draw,
+            _ => StrategyMove::Win,
+        };
 
-    for line in input_lines {
-        decrypted_rounds.push(RPSRound::new_2(line).context("decrypting RPS rounds (correct strategy)")?);
+        RPSRound::from_moves(self.opponent_move, strategy.move_with_strategy(self.opponent_move))
     }
+}
 
-    Ok(decrypted_rounds)
+// Parse the rounds as their lines are read
+fn get_encrypted_rps_rounds(input: Input, mode: ParseMode) -> Result<Vec<EncryptedRound>> {
+    parse_lines(LineReader::new(input)?, mode, |line| EncryptedRound::new(line.text()).map(Some))
 }
 
 
-fn get_game_score(decrypted_rounds: &[RPSRound]) -> i32 {
-    
-    let mut game_score = 0;
-    
-    for round in decrypted_rounds {
-        
-        game_score += round.result.get_score();
+fn get_game_score(mut decrypted_rounds: impl Iterator<Item = RPSRound>) -> Result<i32> {
 
-    }
+    decrypted_rounds.try_fold(0, |game_score, round| add_score(game_score, &round))
+}
+
+// Add the score of a round to that of the game
+fn add_score(game_score: i32, round: &RPSRound) -> Result<i32> {
+
+    let Some(total) = game_score.checked_add(round.result.get_score()) else {
+        return Err(AdventError::simulation("The game score overflows a 32-bit integer").into())
+    };
 
-    game_score
+    Ok(total)
 }
 
 // Part 1:
-pub fn simulate_input_strategy_results() -> Result<i32> {
-
-    let input_lines = get_encrypted_rps_rounds()
-    .context("simulating input strategy results")?;
+pub fn simulate_input_strategy_results(input: impl Into<Input>) -> Result<i32> {
 
-    let decrypted_rounds = decrypt_rps_rounds(&input_lines)
+    // Read the input
+    let encrypted_rounds = get_encrypted_rps_rounds(input.into(), ParseMode::FailFast)
     .context("simulating input strategy results")?;
 
-    Ok(get_game_score(&decrypted_rounds))
+    get_game_score(encrypted_rounds.iter().map(EncryptedRound::decrypt))
 }
 
 /*--- Part Two ---
@@ -374,13 +422,84 @@ everything goes exactly according to your strategy guide?
  // getting the correct encryption keys for the strategy.
 
  // Part 2:
-pub fn simulate_input_strategy_results_2() -> Result<i32> {
+pub fn simulate_input_strategy_results_2(input: impl Into<Input>) -> Result<i32> {
 
-    let input_lines = get_encrypted_rps_rounds()
+    // Read the input
+    let encrypted_rounds = get_encrypted_rps_rounds(input.into(), ParseMode::FailFast)
     .context("simulating input strategy results")?;
 
-    let decrypted_rounds = decrypt_rps_rounds_2(&input_lines)
-    .context("simulating input strategy results")?;
+    get_game_score(encrypted_rounds.iter().map(EncryptedRound::decrypt_2))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed = Vec<EncryptedRound>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+
+    fn parse(&self, input: Input) -> Result<Vec<EncryptedRound>> {
+        get_encrypted_rps_rounds(input, ParseMode::FailFast)
+    }
+
+    fn part_one(&self, encrypted_rounds: &Vec<EncryptedRound>) -> Result<i32> {
+        get_game_score(encrypted_rounds.iter().map(EncryptedRound::decrypt))
+    }
+
+    fn part_two(&self, encrypted_rounds: &Vec<EncryptedRound>) -> Result<i32> {
+        get_game_score(encrypted_rounds.iter().map(EncryptedRound::decrypt_2))
+    }
+
+    // The correct key only allows X, Y and Z in the second column, so it's the stricter one
+    fn validate(&self, input: Input) -> Result<()> {
+        get_encrypted_rps_rounds(input, ParseMode::CollectAll).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounds_from_example() {
+
+        // Part 1: Y is Paper, X is Rock, Z is Scissors
+        assert_eq!(RPSRound::new("A Y").unwrap().result(), RPSResult::SelfWin(8));
+        assert_eq!(RPSRound::new("B X").unwrap().result(), RPSResult::SelfLoss(1));
+        assert_eq!(RPSRound::new("C Z").unwrap().result(), RPSResult::Draw(6));
+
+        // Part 2: Y is draw, X is lose, Z is win
+        assert_eq!(RPSRound::new_2("A Y").unwrap().result(), RPSResult::Draw(4));
+        assert_eq!(RPSRound::new_2("B X").unwrap().result(), RPSResult::SelfLoss(1));
+        assert_eq!(RPSRound::new_2("C Z").unwrap().result(), RPSResult::SelfWin(7));
+    }
+
+    #[test]
+    fn unknown_moves_are_errors() {
+        assert!(RPSRound::new("D X").is_err());
+        assert!(RPSRound::new("A").is_err());
+        assert!(RPSRound::new_2("A A").is_err());
+        assert!(EncryptedRound::new("A A").is_err());
+    }
 
-    Ok(get_game_score(&decrypted_rounds))
-}
\ No newline at end of file
+    #[test]
+    fn encrypted_rounds_with_both_keys() {
+
+        let round = EncryptedRound::new("A Y").unwrap();
+
+        assert_eq!(round.decrypt().result(), RPSRound::new("A Y").unwrap().result());
+        assert_eq!(round.decrypt_2().result(), RPSRound::new_2("A Y").unwrap().result());
+    }
+
+    #[test]
+    fn overflowing_scores_are_errors() {
+
+        let round = RPSRound::new("A Y").unwrap();
+
+        assert_eq!(add_score(0, &round).unwrap(), 8);
+        assert!(add_score(i32::MAX, &round).is_err());
+    }
+}
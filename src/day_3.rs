@@ -1,11 +1,22 @@
 // Standard library
+use std::borrow::Borrow;
 use std::collections::HashMap;
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
 // Crate modules
+use crate::error::{AdventError, LineContext, ParseMode, Span, parse_lines};
 use crate::general::*;
+use crate::params::Params;
+use crate::solution::Solution;
+
+// Item types, in priority order
+const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Elves in a group sharing a badge
+const GROUP_SIZE: usize = 3;
 
 // Part 1:
 /*--- Day 3: Rucksack Reorganization ---
@@ -62,67 +73,78 @@ Find the item type that appears in both compartments of each rucksack. What is t
 sum of the priorities of those item types?
  */
 
-struct ItemTypePriorities {
+pub struct ItemTypePriorities {
     dictionary: HashMap<char, i32>
 }
 
 impl ItemTypePriorities {
 
-    fn new() -> Self {
+    pub fn new() -> Self {
+        Self::from_alphabet(ITEM_TYPES).expect("Default item types are letters")
+    }
+
+    // Priorities from 1 up, in the order of the alphabet, which can only hold letters (the
+    // item types), each at most once
+    pub fn from_alphabet(alphabet: &str) -> Result<Self> {
+
         let mut dictionary: HashMap<char, i32> = HashMap::new();
-    
-        let keys = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
-        let mut values_counter = 1;
+        for (values_counter, char) in (1..).zip(alphabet.chars()) {
+
+            if !char.is_ascii_alphabetic() {
+                return Err(AdventError::validation(format!("Priority alphabet has '{}', which is not an item type", char), None).into())
+            }
 
-        for char in keys.chars() {
-            dictionary.insert(char, values_counter);
-            values_counter += 1;
-        } 
+            if dictionary.insert(char, values_counter).is_some() {
+                return Err(AdventError::validation(format!("Priority alphabet has '{}' more than once", char), None).into())
+            }
+        }
 
-        ItemTypePriorities {dictionary}
+        Ok(ItemTypePriorities {dictionary})
     }
 
-    fn get_item_priority(&self, item_type: char) -> Result<i32> {
+    pub fn get_item_priority(&self, item_type: char) -> Result<i32> {
         
         match self.dictionary.get(&item_type) {
             Some(priority) => Ok(*priority),
-            _ => Err(anyhow!("Unknown item type '{}'", item_type)),
+            _ => Err(AdventError::validation(format!("Unknown item type '{}'", item_type), None).into()),
         }
     }
 
-    fn get_rucksacks_priorities(&self, rucksacks: &[Rucksack]) -> Vec<i32> {
+    // Fails on an item type left out of a custom alphabet
+    pub fn get_rucksacks_priorities(&self, rucksacks: &[Rucksack]) -> Result<Vec<i32>> {
 
         let mut priorities: Vec<i32> = Vec::new();
 
         for rucksack in rucksacks {
-            
-            // Should never panic because Rucksack can't contain invalid Item Types
-            priorities.push(self.get_item_priority(rucksack.wrong_item)
-            .expect("Invalid Item Type in Rucksack!"));
+            priorities.push(self.get_item_priority(rucksack.wrong_item)?);
         }
 
-        priorities
+        Ok(priorities)
 
     }
 
     // Part 2:
-    fn get_badges_priorities(&self, rucksack_groups: &[RucksackGroup]) -> Vec<i32> {
+    pub fn get_badges_priorities(&self, rucksack_groups: &[RucksackGroup]) -> Result<Vec<i32>> {
 
         let mut priorities: Vec<i32> = Vec::new();
 
         for rucksack_group in rucksack_groups {
-
-            // Should never panic because RucksackGroup can't contain invalid Item Types
-            priorities.push(self.get_item_priority(rucksack_group.badge)
-        .expect("Invalid Item Type as Badge!"));
+            priorities.push(self.get_item_priority(rucksack_group.badge)?);
         }
 
-        priorities
+        Ok(priorities)
+    }
+}
+
+impl Default for ItemTypePriorities {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-struct Rucksack {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rucksack {
     compartment_1: String,
     compartment_2: String,
     wrong_item: char,
@@ -130,21 +152,15 @@ struct Rucksack {
 
 impl Rucksack {
 
-    fn new(items: &str) -> Result<Self> {
+    pub fn new(items: &str) -> Result<Self> {
 
         // Check for invalid item types
-        for item in items.chars() {
-            if item.is_ascii_lowercase() || item.is_ascii_uppercase() {
-                continue
-            } else {
-                return Err(anyhow!("Unknown item type '{}'", item))
-            }
-        };
+        check_item_types(items)?;
 
         // See if both compartments include the same amount of items
         let num_items = items.len();
 
-        if num_items % 2 == 0 {
+        if num_items.is_multiple_of(2) {
             
             // Split for compartments and look for the wrong repeated item
             let mid = num_items / 2;
@@ -167,7 +183,7 @@ impl Rucksack {
             let Some(wrong_item) = wrong_item_opt else {
 
                 // There was no repeated item
-                return Err(anyhow!("No repeated item in compartments: '{}'", items))
+                return Err(AdventError::validation("No repeated item in compartments", Some(Span::whole(items))).into())
 
             };
 
@@ -179,58 +195,66 @@ impl Rucksack {
 
         } else {
 
-            Err(anyhow!("Uneven number of items in Rucksack: '{}'", items))
+            Err(AdventError::parse("Uneven number of items in Rucksack", Span::whole(items)).into())
 
         }
     }
 
-}
+    pub fn compartments(&self) -> (&str, &str) {
+        (&self.compartment_1, &self.compartment_2)
+    }
+
+    // The item type found in both compartments
+    pub fn wrong_item(&self) -> char {
+        self.wrong_item
+    }
 
-fn get_rucksack_lines() -> Result<Vec<String>> {
+    // Every item, in the order of the line
+    pub fn items(&self) -> String {
+        format!("{}{}", self.compartment_1, self.compartment_2)
+    }
 
-    // Get input string
-    let input_string = read_text_input("/home/acente/Advent_inputs/day_3_input_1.txt")
-    .context("getting list of rucksacks")?;
+}
 
-    // Turn lines into vector of Strings
-    let mut input_lines: Vec<String> = Vec::new();
+// Point at the first item that is not a letter
+fn check_item_types(items: &str) -> Result<()> {
 
-    for line in input_string.lines() {
-        input_lines.push(line.to_string());
+    for (index, item) in items.chars().enumerate() {
+        if !(item.is_ascii_lowercase() || item.is_ascii_uppercase()) {
+            return Err(AdventError::parse(format!("Unknown item type '{}'", item), Span::new(items, index + 1, 1)).into())
+        }
     }
 
-    Ok(input_lines)
-
+    Ok(())
 }
 
-fn get_rucksack_vec(rucksack_lines: &Vec<String>) -> Result<Vec<Rucksack>> {
+fn get_rucksack_vec<L: Borrow<Line>>(rucksack_lines: impl IntoIterator<Item = Result<L>>, mode: ParseMode) -> Result<Vec<Rucksack>> {
 
-    let mut rucksack_vec: Vec<Rucksack> = Vec::new();
-    
-    // For each line, get a Rucksack
-    for line in rucksack_lines {
+    // For each line, as it's read, get a Rucksack
+    parse_lines(rucksack_lines, mode, |line| Rucksack::new(line.text()).map(Some))
+}
 
-        rucksack_vec.push(Rucksack::new(line).context("getting rucksack contents")?);
+fn get_priority_sum(priorities: &[i32]) -> Result<i32> {
 
-    }
+    let Some(total) = priorities.iter().try_fold(0i32, |total, priority| total.checked_add(*priority)) else {
+        return Err(AdventError::simulation("The sum of priorities overflows a 32-bit integer").into())
+    };
 
-    Ok(rucksack_vec)
+    Ok(total)
 }
 
 // Day 3 Part 1:
-pub fn get_total_priority() -> Result<i32> {
+pub fn get_total_priority(input: impl Into<Input>) -> Result<i32> {
 
     let item_types = ItemTypePriorities::new();
 
-    let rucksack_lines = get_rucksack_lines()
-    .context("getting total rucksack priority sum")?;
-
-    let rucksack_vec = get_rucksack_vec(&rucksack_lines)
+    // Read the input
+    let rucksack_vec = get_rucksack_vec(LineReader::new(input.into())?, ParseMode::FailFast)
     .context("getting total rucksack priority sum")?;
 
-    let priorities_vec = item_types.get_rucksacks_priorities(&rucksack_vec);
+    let priorities_vec = item_types.get_rucksacks_priorities(&rucksack_vec)?;
 
-    Ok(priorities_vec.iter().sum())
+    get_priority_sum(&priorities_vec)
 }
 
 /*--- Part Two ---
@@ -280,132 +304,281 @@ the sum of the priorities of those item types?
 
 
 
- struct RucksackGroup {
-    rucksack_1_items: String,
-    rucksack_2_items: String,
-    rucksack_3_items: String,
+pub struct RucksackGroup {
+    rucksacks: Vec<String>,
     badge: char
 }
 
-fn get_elf_rucksack_groups(rucksack_lines: &Vec<String>) -> Result<Vec<RucksackGroup>> {
+impl RucksackGroup {
+
+    // The badge is the item type in every rucksack of the group, if there is one
+    fn new(rucksacks: Vec<String>) -> Option<Self> {
+
+        let badge = rucksacks.first()?
+        .chars()
+        .find(|item| rucksacks[1..].iter().all(|rucksack| rucksack.contains(*item)))?;
+
+        Some(RucksackGroup { rucksacks, badge })
+    }
+
+    pub fn rucksacks(&self) -> &[String] {
+        &self.rucksacks
+    }
+
+    pub fn badge(&self) -> char {
+        self.badge
+    }
+}
+
+fn badge_error(rucksacks: &[String]) -> AdventError {
+
+    let other_rucksacks: Vec<String> = rucksacks[..rucksacks.len() - 1]
+    .iter()
+    .enumerate()
+    .map(|(index, rucksack)| format!("Elf {} Rucksack: '{}'", index + 1, rucksack))
+    .collect();
+
+    AdventError::validation(
+        format!("Elf group without a badge ({})", other_rucksacks.join(", ")),
+        rucksacks.last().map(|last_rucksack| Span::whole(last_rucksack))
+    )
+}
+
+// Read the lines a group at a time, so only one group is in memory
+fn get_elf_rucksack_groups<L: Borrow<Line>>(rucksack_lines: impl IntoIterator<Item = Result<L>>, group_size: usize) -> Result<Vec<RucksackGroup>> {
 
     // Create groups vector
     let mut rucksack_group_vec: Vec<RucksackGroup> = Vec::new();
+    let mut group_lines: Vec<L> = Vec::with_capacity(group_size);
+
+    for line in rucksack_lines {
 
-    // Get a rucksack counter to assemble groups of 3
-    let mut rucksack_counter = 1;
+        let line = line?;
 
-    // Create optional buffers for the contents
-    let mut buffer_rucksack_1: Option<String> = None;
-    let mut buffer_rucksack_2: Option<String> = None;
+        // Check that rucksacks contain only valid items
+        check_item_types(line.borrow().text()).on_line(line.borrow())?;
 
-    let mut last_flag_3 = false;
-    
-    for rucksack in rucksack_lines {
+        group_lines.push(line);
 
-        // Check that rucksack contains only valid items
-        for item in rucksack.chars() {
-            if item.is_ascii_lowercase() || item.is_ascii_uppercase() {
-                continue
-            } else {
-                return Err(anyhow!("Unknown item type '{}'", item))
-            }
+        if group_lines.len() < group_size {
+            continue
         }
 
-        // Save rucksack 1 contents in buffer
-        if rucksack_counter == 1 {
+        let rucksacks: Vec<String> = group_lines.iter().map(|line| line.borrow().text().to_string()).collect();
 
-            buffer_rucksack_1 = Some(rucksack.clone());
-            rucksack_counter += 1;
-            last_flag_3 = false;
+        let Some(rucksack_group) = RucksackGroup::new(rucksacks) else {
 
-        // Save rucksack 2 contents in buffer
-        } else if rucksack_counter == 2 {
+            let rucksacks: Vec<String> = group_lines.iter().map(|line| line.borrow().text().to_string()).collect();
 
-            buffer_rucksack_2 = Some(rucksack.clone());
-            rucksack_counter += 1;
-            last_flag_3 = false;
-            
+            let mut error = badge_error(&rucksacks);
+            error.locate(group_lines[group_size - 1].borrow());
 
-        // Get contents from previous buffers and get current contents
-        } else {
+            return Err(error.into())
+        };
 
-            let Some(rucksack_1_items) = buffer_rucksack_1.clone() else {
+        // Create this Group
+        rucksack_group_vec.push(rucksack_group);
+        group_lines.clear();
+    }
 
-                return Err(anyhow!("Uninitialized Rucksack!"))
+    if !group_lines.is_empty() || rucksack_group_vec.is_empty() {
+        return Err(AdventError::validation("Last group was incomplete!", None).into())
+    }
 
-            };
+    Ok(rucksack_group_vec)
+}
 
-            let Some(rucksack_2_items) = buffer_rucksack_2.clone() else {
+// Groups of parsed rucksacks, in the order of the input
+fn get_rucksack_groups(rucksack_vec: &[Rucksack], group_size: usize) -> Result<Vec<RucksackGroup>> {
 
-                return Err(anyhow!("Uninitialized Rucksack!"))
-                
-            };
+    if rucksack_vec.is_empty() || !rucksack_vec.len().is_multiple_of(group_size) {
+        return Err(AdventError::validation("Last group was incomplete!", None).into())
+    }
 
-            let rucksack_3_items = rucksack.clone();
+    rucksack_vec
+    .chunks(group_size)
+    .map(|group| {
 
-            // Get badge
-            let mut badge_opt: Option<char> = None;
+        let rucksacks: Vec<String> = group.iter().map(Rucksack::items).collect();
 
-            for item in rucksack_1_items.chars() {
-                if rucksack_2_items.contains(item) && rucksack_3_items.contains(item) {
-                    badge_opt = Some(item);
-                    break
-                }
-            }
+        RucksackGroup::new(rucksacks.clone())
+        .ok_or_else(|| badge_error(&rucksacks).into())
+    })
+    .collect()
+}
 
-            let Some(badge) = badge_opt else {
+// Day 3 Part 2:
+pub fn get_badges_priority(input: impl Into<Input>) -> Result<i32> {
 
-                return Err(
-                    anyhow!(
-                        "Elf group without a badge:\nElf 1 Rucksack: '{}'\nElf 2 Rucksack: '{}'\nElf 3 Rucksack: '{}'\n",
-                        rucksack_1_items,
-                        rucksack_2_items,
-                        rucksack_3_items
-                    )
-                )
-            };
+    let item_types = ItemTypePriorities::new();
 
-            // Create this Group
-            rucksack_group_vec.push(
-                RucksackGroup {
-                    rucksack_1_items,
-                    rucksack_2_items,
-                    rucksack_3_items,
-                    badge
-                }
-            );
+    // Read the input
+    let rucksack_groups_vec = get_elf_rucksack_groups(LineReader::new(input.into())?, GROUP_SIZE)
+    .context("getting total rucksack priority sum")?;
+
+    let priorities_vec = item_types.get_badges_priorities(&rucksack_groups_vec)?;
+
+    get_priority_sum(&priorities_vec)
+}
 
-            // Reset buffers and counter
-            buffer_rucksack_1 = None;
-            buffer_rucksack_2 = None;
-            rucksack_counter = 1;
-            last_flag_3 = true;
+pub struct Day3 {
+    // Item types in priority order, and Elves per group
+    priorities: String,
+    group_size: usize,
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Self {
+            priorities: ITEM_TYPES.to_string(),
+            group_size: GROUP_SIZE,
         }
     }
+}
 
-    if last_flag_3 {
-        
-        Ok(rucksack_group_vec)
-    
-    } else {
-    
-        Err(anyhow!("Last group was incomplete!"))
+impl Day3 {
+
+    // Item types in priority order
+    pub fn priorities(&self) -> &str {
+        &self.priorities
     }
 }
 
-// Day 3 Part 2:
-pub fn get_badges_priority() -> Result<i32> {
+impl Solution for Day3 {
+    type Parsed = Vec<Rucksack>;
+    type PartOne = i32;
+    type PartTwo = i32;
 
-    let item_types = ItemTypePriorities::new();
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+    const PARAMS: &'static [&'static str] = &["priorities", "group_size"];
 
-    let rucksack_lines = get_rucksack_lines()
-    .context("getting total rucksack priority sum")?;
+    fn configure(&mut self, params: &Params) -> Result<()> {
 
-    let rucksack_groups_vec = get_elf_rucksack_groups(&rucksack_lines)
-    .context("getting total rucksack priority sum")?;
+        // Check every parameter before changing any, so a failure leaves the day as it was
+        let priorities = params.string("priorities", &self.priorities)?;
+        ItemTypePriorities::from_alphabet(&priorities)?;
+
+        let group_size = params.usize("group_size", self.group_size)?;
+
+        self.priorities = priorities;
+        self.group_size = group_size;
+
+        Ok(())
+    }
+
+    // Rucksacks are parsed as their lines are read; an input must be valid for both parts
+    fn parse(&self, input: Input) -> Result<Vec<Rucksack>> {
+        get_rucksack_vec(LineReader::new(input)?, ParseMode::FailFast)
+    }
+
+    fn part_one(&self, rucksack_vec: &Vec<Rucksack>) -> Result<i32> {
+
+        let priorities_vec = ItemTypePriorities::from_alphabet(&self.priorities)?.get_rucksacks_priorities(rucksack_vec)?;
+
+        get_priority_sum(&priorities_vec)
+    }
+
+    fn part_two(&self, rucksack_vec: &Vec<Rucksack>) -> Result<i32> {
+
+        let rucksack_groups_vec = get_rucksack_groups(rucksack_vec, self.group_size)
+        .context("getting total badge priority sum")?;
+
+        let priorities_vec = ItemTypePriorities::from_alphabet(&self.priorities)?.get_badges_priorities(&rucksack_groups_vec)?;
+
+        get_priority_sum(&priorities_vec)
+    }
+
+    // Every rucksack is checked on its own first, then the groups of three
+    fn validate(&self, input: Input) -> Result<()> {
+
+        let rucksack_lines = read_lines(input)?;
+
+        get_rucksack_vec(rucksack_lines.iter().map(Ok), ParseMode::CollectAll)?;
 
-    let priorities_vec = item_types.get_badges_priorities(&rucksack_groups_vec);
+        get_elf_rucksack_groups(rucksack_lines.iter().map(Ok), self.group_size).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../tests/fixtures/day_3_example.txt");
+
+    #[test]
+    fn wrong_items_from_example() {
+
+        let rucksack_vec = get_rucksack_vec(LineReader::new(EXAMPLE.into()).unwrap(), ParseMode::FailFast).unwrap();
+
+        let wrong_items: String = rucksack_vec.iter().map(|rucksack| rucksack.wrong_item()).collect();
+
+        assert_eq!(wrong_items, "pLPvts");
+        assert_eq!(rucksack_vec[0].compartments(), ("vJrwpWtwJgWr", "hcsFMMfFFhFp"));
+    }
+
+    #[test]
+    fn item_priorities() {
+
+        let item_types = ItemTypePriorities::new();
+
+        assert_eq!(item_types.get_item_priority('a').unwrap(), 1);
+        assert_eq!(item_types.get_item_priority('z').unwrap(), 26);
+        assert_eq!(item_types.get_item_priority('A').unwrap(), 27);
+        assert_eq!(item_types.get_item_priority('Z').unwrap(), 52);
+        assert!(item_types.get_item_priority('1').is_err());
+    }
+
+    #[test]
+    fn badges_from_example() {
+
+        let rucksack_groups = get_elf_rucksack_groups(LineReader::new(EXAMPLE.into()).unwrap(), 3).unwrap();
+
+        let badges: String = rucksack_groups.iter().map(|group| group.badge()).collect();
 
-    Ok(priorities_vec.iter().sum())
-}
\ No newline at end of file
+        assert_eq!(badges, "rZ");
+
+        // The same groups from parsed rucksacks
+        let rucksack_vec = Day3::default().parse(EXAMPLE.into()).unwrap();
+        let badges: String = get_rucksack_groups(&rucksack_vec, 3).unwrap().iter().map(|group| group.badge()).collect();
+
+        assert_eq!(badges, "rZ");
+    }
+
+    #[test]
+    fn invalid_rucksacks_are_errors() {
+        assert!(Rucksack::new("abc").is_err());
+        assert!(Rucksack::new("abcd").is_err());
+        assert!(Rucksack::new("ab1a").is_err());
+        assert!(get_elf_rucksack_groups(LineReader::new("aa\nab".into()).unwrap(), 3).is_err());
+        assert!(get_elf_rucksack_groups(LineReader::new("aa\nab\nba\nab".into()).unwrap(), 3).is_err());
+    }
+
+    #[test]
+    fn overflowing_priorities_are_errors() {
+        assert_eq!(get_priority_sum(&[16, 1, 22]).unwrap(), 39);
+        assert!(get_priority_sum(&[i32::MAX, 1]).is_err());
+    }
+
+    #[test]
+    fn configured_alphabet_and_group_size() {
+
+        let rucksack_vec = Day3::default().parse(EXAMPLE.into()).unwrap();
+
+        let mut day = Day3::default();
+        day.configure(&Params::new().with("priorities", "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba").with("group_size", 2)).unwrap();
+
+        // p, L, P, v, t and s, then the badges of the three pairs: r, q and J
+        assert_eq!(day.part_one(&rucksack_vec).unwrap(), 37 + 15 + 11 + 31 + 33 + 34);
+        assert_eq!(day.part_two(&rucksack_vec).unwrap(), 35 + 36 + 17);
+
+        assert!(day.configure(&Params::new().with("priorities", "abca")).is_err());
+        assert!(day.configure(&Params::new().with("priorities", "ab1")).is_err());
+        assert!(day.configure(&Params::new().with("priorities", "abc").with("group_size", 0)).is_err());
+
+        // A failed configuration keeps the previous one
+        assert_eq!(day.part_one(&rucksack_vec).unwrap(), 37 + 15 + 11 + 31 + 33 + 34);
+        assert_eq!(day.part_two(&rucksack_vec).unwrap(), 35 + 36 + 17);
+        assert!(ItemTypePriorities::from_alphabet("ab").unwrap().get_item_priority('c').is_err());
+    }
+}
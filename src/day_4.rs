@@ -2,10 +2,13 @@
 
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
 
 // Crate modules
+use crate::error::{AdventError, ParseMode, Span, parse_lines};
 use crate::general::*;
+use crate::solution::Solution;
 
 /*--- Day 4: Camp Cleanup ---
 
@@ -68,7 +71,8 @@ most in need of reconsideration. In this example, there are 2 such pairs.
 In how many assignment pairs does one range fully contain the other?
  */
 
-struct AssignmentPair {
+#[derive(Serialize, Deserialize)]
+pub struct AssignmentPair {
     elf_1: (i32, i32),
     elf_2: (i32, i32),
     total_overlap: bool,
@@ -78,16 +82,16 @@ struct AssignmentPair {
 
 impl AssignmentPair {
     
-    fn new(assignment_line: &str) -> Result<Self> {
+    pub fn new(assignment_line: &str) -> Result<Self> {
 
         let limits: Vec<&str> = assignment_line.split(&[',', '-'][..]).collect();
 
         if limits.len() == 4 {
 
-            let elf_1_lower = limits[0].parse::<i32>().context("an assignment limit is not a number")?;
-            let elf_1_upper = limits[1].parse::<i32>().context("an assignment limit is not a number")?;
-            let elf_2_lower = limits[2].parse::<i32>().context("an assignment limit is not a number")?;
-            let elf_2_upper = limits[3].parse::<i32>().context("an assignment limit is not a number")?;
+            let elf_1_lower = parse_limit(assignment_line, limits[0])?;
+            let elf_1_upper = parse_limit(assignment_line, limits[1])?;
+            let elf_2_lower = parse_limit(assignment_line, limits[2])?;
+            let elf_2_upper = parse_limit(assignment_line, limits[3])?;
 
             let elf_1 = (elf_1_lower, elf_1_upper);
             let elf_2 = (elf_2_lower, elf_2_upper);
@@ -108,46 +112,45 @@ impl AssignmentPair {
             )
         } else {
 
-            Err(anyhow!("An assignment line doesn't have exactly 4 limits: '{}'", assignment_line))
+            Err(AdventError::parse("An assignment line doesn't have exactly 4 limits", Span::whole(assignment_line)).into())
         }
     }
-}
-
-fn fully_contains(range_1: (i32, i32), range_2: (i32, i32)) -> bool {
-
-    if range_1.0 <= range_2.0 && range_1.1 >= range_2.1 { true }
-    else if range_2.0 <= range_1.0 && range_2.1 >= range_1.1 {true }
-    else { false }
-
-}
 
-fn get_assignment_lines() -> Result<Vec<String>> {
+    pub fn elf_1(&self) -> (i32, i32) {
+        self.elf_1
+    }
 
-    // Get input file as a string
-    let input_string = read_text_input("/home/acente/Advent_inputs/day_4_input_1.txt")
-    .context("getting assignment pairs list")?;
+    pub fn elf_2(&self) -> (i32, i32) {
+        self.elf_2
+    }
 
-    // Turn lines into vector of Strings
-    let mut input_lines: Vec<String> = Vec::new();
+    pub fn total_overlap(&self) -> bool {
+        self.total_overlap
+    }
 
-    for line in input_string.lines() {
-        input_lines.push(line.to_string());
+    pub fn simple_overlap(&self) -> bool {
+        self.simple_overlap
     }
 
-    Ok(input_lines)
 }
 
-fn get_assignments_vec(assignment_lines: &Vec<String>) -> Result<Vec<AssignmentPair>> {
+pub fn fully_contains(range_1: (i32, i32), range_2: (i32, i32)) -> bool {
 
-    let mut assignments_vec: Vec<AssignmentPair> = Vec::new(); 
+    (range_1.0 <= range_2.0 && range_1.1 >= range_2.1) || 
+    (range_2.0 <= range_1.0 && range_2.1 >= range_1.1)
 
-    for assignment in assignment_lines {
-
-        assignments_vec.push(AssignmentPair::new(assignment).context("getting assingment pairs")?);
+}
 
-    }
+// Parse one limit of an assignment line, pointing at it if it's not a number
+fn parse_limit(assignment_line: &str, limit: &str) -> Result<i32> {
+    limit.parse::<i32>().map_err(|error| {
+        AdventError::parse(format!("An assignment limit is not a number ({})", error), Span::of(assignment_line, limit)).into()
+    })
+}
 
-    Ok(assignments_vec)
+// Parse the assignment pairs as the lines are read
+fn get_assignments_vec(assignment_lines: impl Iterator<Item = Result<Line>>, mode: ParseMode) -> Result<Vec<AssignmentPair>> {
+    parse_lines(assignment_lines, mode, |assignment| AssignmentPair::new(assignment.text()).map(Some))
 }
 
 fn get_number_of_complete_overlaps(assignments_vec: &[AssignmentPair]) -> i32 {
@@ -163,12 +166,13 @@ fn get_number_of_complete_overlaps(assignments_vec: &[AssignmentPair]) -> i32 {
     complete_overlaps
 }
 
-pub fn get_complete_overlaps() -> Result<i32> {
+pub fn get_complete_overlaps(input: impl Into<Input>) -> Result<i32> {
 
-    let assignment_lines = get_assignment_lines()
+    // Read the input, line by line
+    let assignment_lines = LineReader::new(input.into())
     .context("counting complete overlaps in assignments")?;
 
-    let assignments_vec = get_assignments_vec(&assignment_lines)
+    let assignments_vec = get_assignments_vec(assignment_lines, ParseMode::FailFast)
     .context("counting complete overlaps in assignments")?;
 
     Ok(get_number_of_complete_overlaps(&assignments_vec))
@@ -194,22 +198,15 @@ In how many assignment pairs do the ranges overlap?
  */
 
  // Part 2:
-fn partially_contains(range_1: (i32, i32), range_2: (i32, i32)) -> bool {
+pub fn partially_contains(range_1: (i32, i32), range_2: (i32, i32)) -> bool {
 
     let actual_range_1 = range_1.0..=range_1.1;
     let actual_range_2 = range_2.0..=range_2.1;
 
-    if actual_range_1.contains(&range_2.0) || 
+    actual_range_1.contains(&range_2.0) || 
     actual_range_1.contains(&range_2.1) ||
     actual_range_2.contains(&range_1.0) ||
-    actual_range_2.contains(&range_1.1) {
-
-        true
-
-    } else {
-        
-        false
-    }
+    actual_range_2.contains(&range_1.1)
 }
 
 // Part 2:
@@ -226,13 +223,70 @@ fn get_number_of_partial_overlaps(assignments_vec: &[AssignmentPair]) -> i32 {
     partial_overlaps
 }
 
-pub fn get_partial_overlaps() -> Result<i32> {
+pub fn get_partial_overlaps(input: impl Into<Input>) -> Result<i32> {
 
-    let assignment_lines = get_assignment_lines()
-    .context("counting complete overlaps in assignments")?;
+    // Read the input, line by line
+    let assignment_lines = LineReader::new(input.into())
+    .context("counting partial overlaps in assignments")?;
 
-    let assignments_vec = get_assignments_vec(&assignment_lines)
+    let assignments_vec = get_assignments_vec(assignment_lines, ParseMode::FailFast)
     .context("counting complete overlaps in assignments")?;
 
     Ok(get_number_of_partial_overlaps(&assignments_vec))
-}
\ No newline at end of file
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<AssignmentPair>;
+    type PartOne = i32;
+    type PartTwo = i32;
+
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Camp Cleanup";
+
+    fn parse(&self, input: Input) -> Result<Vec<AssignmentPair>> {
+        get_assignments_vec(LineReader::new(input)?, ParseMode::FailFast)
+    }
+
+    fn part_one(&self, assignments_vec: &Vec<AssignmentPair>) -> Result<i32> {
+        Ok(get_number_of_complete_overlaps(assignments_vec))
+    }
+
+    fn part_two(&self, assignments_vec: &Vec<AssignmentPair>) -> Result<i32> {
+        Ok(get_number_of_partial_overlaps(assignments_vec))
+    }
+
+    fn validate(&self, input: Input) -> Result<()> {
+        get_assignments_vec(LineReader::new(input)?, ParseMode::CollectAll).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn full_containment_from_example() {
+        assert!(fully_contains((2, 8), (3, 7)));
+        assert!(fully_contains((6, 6), (4, 6)));
+        assert!(!fully_contains((2, 4), (6, 8)));
+        assert!(!fully_contains((5, 7), (7, 9)));
+    }
+
+    #[test]
+    fn partial_overlap_from_example() {
+        assert!(!partially_contains((2, 4), (6, 8)));
+        assert!(!partially_contains((2, 3), (4, 5)));
+        assert!(partially_contains((5, 7), (7, 9)));
+        assert!(partially_contains((2, 8), (3, 7)));
+        assert!(partially_contains((6, 6), (4, 6)));
+        assert!(partially_contains((2, 6), (4, 8)));
+    }
+
+    #[test]
+    fn invalid_assignment_lines_are_errors() {
+        assert!(AssignmentPair::new("2-4,6").is_err());
+        assert!(AssignmentPair::new("2-4,6-x").is_err());
+    }
+}
@@ -1,11 +1,17 @@
 // Standard library
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 // Crate modules
+use crate::error::{AdventError, ParseMode, Span, parse_lines};
 use crate::general::*;
+use crate::solution::Solution;
 
 
 /*--- Day 5: Supply Stacks ---
@@ -88,7 +94,8 @@ After the rearrangement procedure completes, what crate ends up on top of each
 stack?
  */
 
-struct Stack {
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Stack {
     num_stacks: i32,
     stack_tags: Vec<String>,
     stack_map: HashMap<String, Vec<char>>
@@ -96,15 +103,24 @@ struct Stack {
 
 impl Stack {
 
-    fn new() -> Result<Self> {
+    // Read the stack drawing at the start of an input
+    pub fn new(input: impl Into<Input>) -> Result<Self> {
 
-        let lines = get_input_lines("/home/acente/Advent_inputs/day_5_input_1.txt")
+        let mut lines = LineReader::new(input.into())
         .context("reading stack drawing")?;
 
-        let (num_stacks, stack_tags) = find_stacks(&lines)
+        let drawing_lines = get_drawing_lines(&mut lines)
         .context("reading stack drawing")?;
 
-        let box_lines = get_box_lines(&lines)
+        Self::from_drawing(&drawing_lines)
+    }
+
+    pub fn from_drawing(drawing_lines: &[Line]) -> Result<Self> {
+
+        let (num_stacks, stack_tags) = find_stacks(drawing_lines)
+        .context("reading stack drawing")?;
+
+        let box_lines = get_box_lines(drawing_lines)
         .context("reading stack drawing")?;
 
         let box_rows = get_box_rows(&box_lines, num_stacks)
@@ -128,14 +144,21 @@ impl Stack {
         )
     }
 
-    fn move_boxes(&mut self, move_vec: &[Movement]) -> Result<String> {
+    pub fn move_boxes(&mut self, move_vec: &[Movement]) -> Result<String> {
 
         for movement in move_vec {
 
+            debug!(
+                crates = movement.number_of_crates,
+                from = %movement.origin_stack,
+                to = %movement.destination_stack,
+                "moving crates"
+            );
+
             let mut all_moved_boxes: Vec<char> = Vec::new();
 
             let Some(origin_stack) = self.stack_map.get_mut(&movement.origin_stack) else {
-                return Err(anyhow!("Stack {} not found in stack map.", movement.origin_stack))
+                return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.origin_stack)).into())
             };
 
             for _ in 0..movement.number_of_crates {
@@ -150,26 +173,26 @@ impl Stack {
             match self.stack_map.get_mut(&movement.destination_stack) {
                 Some(destination_stack) => {
 
-                    for moved_box in 0..all_moved_boxes.len() {
+                    for moved_box in &all_moved_boxes {
 
-                        destination_stack.push(all_moved_boxes[moved_box]);
+                        destination_stack.push(*moved_box);
                     }
                 }
                 None => {
 
                     let Some(origin_stack) = self.stack_map.get_mut(&movement.origin_stack) else {
-                        return Err(anyhow!("Stack {} not found in stack map.", movement.origin_stack))
+                        return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.origin_stack)).into())
                     };
 
                     all_moved_boxes.reverse();
 
-                    for moved_box in 0..all_moved_boxes.len() {
+                    for moved_box in &all_moved_boxes {
 
-                        origin_stack.push(all_moved_boxes[moved_box]);
+                        origin_stack.push(*moved_box);
 
                     }
 
-                    return Err(anyhow!("Stack {} not found in stack map.", movement.destination_stack))
+                    return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.destination_stack)).into())
                 }
             }
         }
@@ -178,14 +201,21 @@ impl Stack {
     }
 
     // Part 2:
-    fn move_boxes_with_cratemover_9001(&mut self, move_vec: &[Movement]) -> Result<String> {
+    pub fn move_boxes_with_cratemover_9001(&mut self, move_vec: &[Movement]) -> Result<String> {
 
         for movement in move_vec {
 
+            debug!(
+                crates = movement.number_of_crates,
+                from = %movement.origin_stack,
+                to = %movement.destination_stack,
+                "moving crates"
+            );
+
             let mut all_moved_boxes: Vec<char> = Vec::new();
 
             let Some(origin_stack) = self.stack_map.get_mut(&movement.origin_stack) else {
-                return Err(anyhow!("Stack {} not found in stack map.", movement.origin_stack))
+                return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.origin_stack)).into())
             };
 
             for _ in 0..movement.number_of_crates {
@@ -200,24 +230,24 @@ impl Stack {
             match self.stack_map.get_mut(&movement.destination_stack) {
                 Some(destination_stack) => {
 
-                    for moved_box in 0..all_moved_boxes.len() {
+                    for moved_box in &all_moved_boxes {
 
-                        destination_stack.push(all_moved_boxes[moved_box]);
+                        destination_stack.push(*moved_box);
                     }
                 }
                 None => {
 
                     let Some(origin_stack) = self.stack_map.get_mut(&movement.origin_stack) else {
-                        return Err(anyhow!("Stack {} not found in stack map.", movement.origin_stack))
+                        return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.origin_stack)).into())
                     };
 
-                    for moved_box in 0..all_moved_boxes.len() {
+                    for moved_box in &all_moved_boxes {
 
-                        origin_stack.push(all_moved_boxes[moved_box]);
+                        origin_stack.push(*moved_box);
 
                     }
 
-                    return Err(anyhow!("Stack {} not found in stack map.", movement.destination_stack))
+                    return Err(AdventError::simulation(format!("Stack {} not found in stack map.", movement.destination_stack)).into())
                 }
             }
         }
@@ -225,7 +255,7 @@ impl Stack {
         Ok(self.top_boxes())
     }
 
-    fn top_boxes(&self) -> String {
+    pub fn top_boxes(&self) -> String {
         
         let mut top_boxes = String::new();
 
@@ -246,9 +276,51 @@ impl Stack {
 
         top_boxes
     }
+
+    pub fn num_stacks(&self) -> i32 {
+        self.num_stacks
+    }
+
+    pub fn stack_tags(&self) -> &[String] {
+        &self.stack_tags
+    }
+
+    // Boxes of a stack, from bottom to top
+    pub fn stack(&self, stack_tag: &str) -> Option<&[char]> {
+        self.stack_map.get(stack_tag).map(|boxes| boxes.as_slice())
+    }
+
+}
+
+// Drawn as in the puzzle input, highest boxes first
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        let stacks: Vec<&[char]> = self.stack_tags
+        .iter()
+        .map(|stack_tag| self.stack(stack_tag).unwrap_or_default())
+        .collect();
+
+        let height = stacks.iter().map(|boxes| boxes.len()).max().unwrap_or(0);
+
+        for row in (0..height).rev() {
+
+            let cells: Vec<String> = stacks
+            .iter()
+            .map(|boxes| boxes.get(row).map_or("   ".to_string(), |box_tag| format!("[{}]", box_tag)))
+            .collect();
+
+            writeln!(f, "{}", cells.join(" ").trim_end())?;
+        }
+
+        let tags: Vec<String> = self.stack_tags.iter().map(|stack_tag| format!(" {} ", stack_tag)).collect();
+
+        write!(f, "{}", tags.join(" ").trim_end())
+    }
 }
 
-struct Movement {
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Movement {
     number_of_crates: i32,
     origin_stack: String,
     destination_stack: String
@@ -256,40 +328,76 @@ struct Movement {
 
 impl Movement {
 
-    fn new(move_line: &str) -> Result<Self> {
+    pub fn new(move_line: &str) -> Result<Self> {
         
         let move_line_parts: Vec<&str> = move_line.split_whitespace().collect();
 
-        if move_line_parts[0] == "move" && move_line_parts[2] == "from" && move_line_parts[4] == "to" {
+        let ["move", number_of_crates, "from", origin_stack, "to", destination_stack] = move_line_parts[..] else {
+            return Err(AdventError::parse("Unknown move line format", Span::whole(move_line)).into())
+        };
 
-            let number_of_crates = move_line_parts[1].parse::<i32>()
-            .context("decoding move line")?;
+        let number_of_crates = number_of_crates.parse::<i32>()
+        .map_err(|error| {
+            AdventError::parse(format!("The number of crates is not a number ({})", error), Span::of(move_line, number_of_crates))
+        })?;
 
-            let origin_stack = move_line_parts[3].to_string();
+        Ok(
+            Movement {
+                number_of_crates,
+                origin_stack: origin_stack.to_string(),
+                destination_stack: destination_stack.to_string()
+            }
+        )
+    }
 
-            let destination_stack = move_line_parts[5].to_string();
+    pub fn number_of_crates(&self) -> i32 {
+        self.number_of_crates
+    }
 
-            Ok(
-                Movement {
-                    number_of_crates,
-                    origin_stack,
-                    destination_stack
-                }
-            )
+    pub fn origin_stack(&self) -> &str {
+        &self.origin_stack
+    }
 
-        } else {
+    pub fn destination_stack(&self) -> &str {
+        &self.destination_stack
+    }
+
+}
 
-            Err(anyhow!("Unknown move line format: '{}'", move_line))
+// Written as a line of the procedure
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "move {} from {} to {}", self.number_of_crates, self.origin_stack, self.destination_stack)
+    }
+}
+
+// Read the lines of the stack drawing, up to the empty line before the procedure
+fn get_drawing_lines(lines: &mut impl Iterator<Item = Result<Line>>) -> Result<Vec<Line>> {
+
+    let mut drawing_lines: Vec<Line> = Vec::new();
+
+    for line in lines {
+
+        let line = line?;
+
+        if line.text().is_empty() {
+            break
         }
+
+        drawing_lines.push(line);
     }
+
+    Ok(drawing_lines)
 }
 
-fn find_stacks(lines: &Vec<String>) -> Result<(i32, Vec<String>)> {
+fn find_stacks(lines: &[Line]) -> Result<(i32, Vec<String>)> {
 
     let mut stack_tags_opt: Option<Vec<String>> = None;
     
     for line in lines {
 
+        let line = line.text();
+
         if line.contains(" 1   2  ") {
 
             stack_tags_opt = Some(
@@ -304,20 +412,20 @@ fn find_stacks(lines: &Vec<String>) -> Result<(i32, Vec<String>)> {
 
     let Some(stack_tags) = stack_tags_opt else {
 
-        return Err(anyhow!("Stack tags line not found in ' 1   2 ...' format."))
+        return Err(AdventError::parse_input("Stack tags line not found in ' 1   2 ...' format.").into())
 
     };
 
     Ok((stack_tags.len() as i32, stack_tags))
 }
 
-fn get_box_lines(lines: &Vec<String>) -> Result<Vec<String>> {
+fn get_box_lines(lines: &[Line]) -> Result<Vec<Line>> {
 
-    let mut box_lines: Vec<String> = Vec::new();
+    let mut box_lines: Vec<Line> = Vec::new();
 
     for line in lines {
         
-        if line.contains("[") {
+        if line.text().contains("[") {
 
             box_lines.push(line.clone());
 
@@ -327,13 +435,13 @@ fn get_box_lines(lines: &Vec<String>) -> Result<Vec<String>> {
     Ok(box_lines)
 }
 
-fn get_box_rows (box_lines: &[String], num_stacks: i32) -> Result<Vec<Vec<char>>> {
+fn get_box_rows (box_lines: &[Line], num_stacks: i32) -> Result<Vec<Vec<char>>> {
     
     let mut box_rows: Vec<Vec<char>> = Vec::new();
 
     for line in box_lines {
 
-        let raw_tag_row: Vec<char> = line.chars().collect();
+        let raw_tag_row: Vec<char> = line.text().chars().collect();
 
         let mut tag_row: Vec<char> = Vec::new();
 
@@ -341,7 +449,8 @@ fn get_box_rows (box_lines: &[String], num_stacks: i32) -> Result<Vec<Vec<char>>
 
             let box_tag_index = (stack - 1) * 4 + 1;
 
-            tag_row.push(raw_tag_row[box_tag_index]);
+            // Rows are often cut short when the last stacks have no box at that height
+            tag_row.push(raw_tag_row.get(box_tag_index).copied().unwrap_or(' '));
 
         }
 
@@ -377,49 +486,50 @@ fn get_box_stacks(box_rows: &Vec<Vec<char>>, num_stacks: i32) -> Result<Vec<Vec<
     Ok(box_stacks)
 }
 
-fn get_move_lines() -> Result<Vec<String>> {
-    
-    // Get all lines
-    let lines = get_input_lines("/home/acente/Advent_inputs/day_5_input_1.txt")
-    .context("reading stack drawing")?;
-
-    let mut move_lines: Vec<String> = Vec::new();
+// Parse the rearrangement procedure as its lines are read
+fn get_move_vector(move_lines: impl Iterator<Item = Result<Line>>, mode: ParseMode) -> Result<Vec<Movement>> {
 
-    for line in lines {
+    parse_lines(move_lines, mode, |move_line| {
 
-        if line.contains("move") {
-            move_lines.push(line.clone());
+        if move_line.text().contains("move") {
+            Movement::new(move_line.text()).map(Some)
+        } else {
+            Ok(None)
         }
-    }
-
-    Ok(move_lines)
+    })
 }
 
-fn get_move_vector(move_lines: &Vec<String>) -> Result<Vec<Movement>> {
+// Read the stack drawing, then the rearrangement procedure, in a single pass over the input
+fn get_stack_and_moves(input: Input, mode: ParseMode) -> Result<(Stack, Vec<Movement>)> {
 
-    let mut move_vector: Vec<Movement> = Vec::new();
+    let mut lines = LineReader::new(input)?;
 
-    for move_line in move_lines {
+    let drawing_lines = get_drawing_lines(&mut lines)
+    .context("reading stack drawing")?;
 
-        move_vector.push(Movement::new(move_line).context("creating move vector")?);
-    }
+    let stack = Stack::from_drawing(&drawing_lines)?;
+
+    let moves_vec = get_move_vector(lines, mode)
+    .context("reading rearrangement procedure")?;
+
+    Ok((stack, moves_vec))
+}
 
-    Ok(move_vector)
+// Both parts start from the same drawing and procedure, parsed once per process
+fn get_cached_stack_and_moves(input: Input) -> Result<Arc<(Stack, Vec<Movement>)>> {
+    ParseCache::global().get_or_parse("day_5", input, |input| get_stack_and_moves(input, ParseMode::FailFast))
 }
 
 // Part 1:
-pub fn get_stack_tops() -> Result<String> {
+pub fn get_stack_tops(input: impl Into<Input>) -> Result<String> {
 
-    let mut stack = Stack::new()
+    // Read the input, or reuse it if the other part already parsed it
+    let parsed = get_cached_stack_and_moves(input.into())
     .context("moving boxes")?;
 
-    let move_lines = get_move_lines()
-    .context("moving boxes")?;
+    let (stack, moves_vec) = parsed.as_ref();
 
-    let moves_vec = get_move_vector(&move_lines)
-    .context("moving boxes")?;
-
-    stack.move_boxes(&moves_vec)
+    stack.clone().move_boxes(moves_vec)
 
 }
 
@@ -487,17 +597,100 @@ stack?
  */
 
 // Part 2:
-pub fn get_stack_tops_with_cratemover_9001() -> Result<String> {
+pub fn get_stack_tops_with_cratemover_9001(input: impl Into<Input>) -> Result<String> {
 
-    let mut stack = Stack::new()
+    // Read the input, or reuse it if the other part already parsed it
+    let parsed = get_cached_stack_and_moves(input.into())
     .context("moving boxes")?;
 
-    let move_lines = get_move_lines()
-    .context("moving boxes")?;
+    let (stack, moves_vec) = parsed.as_ref();
 
-    let moves_vec = get_move_vector(&move_lines)
-    .context("moving boxes")?;
+    stack.clone().move_boxes_with_cratemover_9001(moves_vec)
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = (Stack, Vec<Movement>);
+    type PartOne = String;
+    type PartTwo = String;
 
-    stack.move_boxes_with_cratemover_9001(&moves_vec)
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+
+    fn parse(&self, input: Input) -> Result<(Stack, Vec<Movement>)> {
+        get_stack_and_moves(input, ParseMode::FailFast)
+    }
+
+    // The stack drawing must be readable before the procedure can be checked
+    fn validate(&self, input: Input) -> Result<()> {
+        get_stack_and_moves(input, ParseMode::CollectAll).map(|_| ())
+    }
+
+    fn part_one(&self, (stack, moves_vec): &(Stack, Vec<Movement>)) -> Result<String> {
+        stack.clone().move_boxes(moves_vec)
+    }
 
-}
\ No newline at end of file
+    fn part_two(&self, (stack, moves_vec): &(Stack, Vec<Movement>)) -> Result<String> {
+        stack.clone().move_boxes_with_cratemover_9001(moves_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../tests/fixtures/day_5_example.txt");
+
+    #[test]
+    fn stack_drawing_from_example() {
+
+        let stack = Stack::new(EXAMPLE).unwrap();
+
+        assert_eq!(stack.num_stacks(), 3);
+        assert_eq!(stack.stack("1"), Some(&['Z', 'N'][..]));
+        assert_eq!(stack.stack("2"), Some(&['M', 'C', 'D'][..]));
+        assert_eq!(stack.stack("3"), Some(&['P'][..]));
+        assert_eq!(stack.top_boxes(), "NDP");
+        assert_eq!(stack.to_string(), "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+    }
+
+    #[test]
+    fn move_line() {
+
+        let movement = Movement::new("move 3 from 1 to 3").unwrap();
+
+        assert_eq!(movement.number_of_crates(), 3);
+        assert_eq!(movement.origin_stack(), "1");
+        assert_eq!(movement.destination_stack(), "3");
+        assert_eq!(movement.to_string(), "move 3 from 1 to 3");
+
+        assert!(Movement::new("take 3 from 1 to 3").is_err());
+        assert!(Movement::new("move 3 from 1").is_err());
+        assert!(Movement::new("move").is_err());
+    }
+
+    #[test]
+    fn rows_cut_short_have_empty_cells() {
+
+        let stack = Stack::new("[A]\n[B] [C]\n 1   2   3\n").unwrap();
+
+        assert_eq!(stack.stack("1"), Some(&['B', 'A'][..]));
+        assert_eq!(stack.stack("2"), Some(&['C'][..]));
+        assert_eq!(stack.stack("3"), Some(&[][..]));
+    }
+
+    #[test]
+    fn unknown_destination_leaves_stacks_untouched() {
+
+        let mut stack = Stack::new(EXAMPLE).unwrap();
+
+        let moves_vec = vec![Movement::new("move 2 from 2 to 9").unwrap()];
+
+        assert!(stack.move_boxes(&moves_vec).is_err());
+        assert_eq!(stack.stack("2"), Some(&['M', 'C', 'D'][..]));
+
+        assert!(stack.move_boxes_with_cratemover_9001(&moves_vec).is_err());
+        assert_eq!(stack.stack("2"), Some(&['M', 'C', 'D'][..]));
+    }
+}
@@ -2,10 +2,15 @@
 
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::trace;
 
 // Crate modules
+use crate::error::AdventError;
 use crate::general::*;
+use crate::params::Params;
+use crate::solution::Solution;
 
 /*--- Day 6: Tuning Trouble ---
 
@@ -62,33 +67,41 @@ How many characters need to be processed before the first start-of-packet marker
 is detected?
  */
 
-struct ElvenDevice {
+#[derive(Serialize, Deserialize)]
+pub struct ElvenDevice {
     datastream: Vec<char>,
 }
 
 impl ElvenDevice {
 
-    fn new() -> Result<Self> {
+    pub fn new(input: &str) -> Self {
 
-        let new_device = Self {
-            datastream: read_text_input("/home/acente/Advent_inputs/day_6_input_1.txt")
-            .context("getting datastream")?.chars().collect(),
-        };
+        Self {
+            datastream: input.trim_end().chars().collect(),
+        }
+    }
 
-        Ok(new_device)
+    pub fn datastream(&self) -> &[char] {
+        &self.datastream
     }
 
-    fn get_start_of_packet(&self, start_of_packet_size: usize, start_of_packet_checker: &dyn Fn(&[char]) -> bool) -> Option<usize> {
+    pub fn get_start_of_packet(&self, start_of_packet_size: usize, start_of_packet_checker: &dyn Fn(&[char]) -> bool) -> Option<usize> {
+
+        // A marker has at least one character
+        if start_of_packet_size == 0 {
+            return None
+        }
 
         let mut start_of_packet: Option<usize> = None;
 
-        for character_index in start_of_packet_size..=self.datastream.len() - start_of_packet_size {
+        for character_index in start_of_packet_size..=self.datastream.len() {
 
             let datastream_start_index = character_index - start_of_packet_size;
-            let datastream_end_index = character_index - 1;
-            let current_packet = &self.datastream[datastream_start_index..=datastream_end_index];
+            let current_packet = &self.datastream[datastream_start_index..character_index];
+
+            trace!(end = character_index, window = %current_packet.iter().collect::<String>(), "checking marker window");
 
-            if start_of_packet_checker(&current_packet) {
+            if start_of_packet_checker(current_packet) {
 
                 start_of_packet = Some(character_index);
                 break
@@ -98,7 +111,7 @@ impl ElvenDevice {
         start_of_packet
     }
 
-    fn unrepeated_start_of_packet(packet_query: &[char]) -> bool {
+    pub fn unrepeated_start_of_packet(packet_query: &[char]) -> bool {
         
         let mut seen_characters: Vec<char> = Vec::new();
 
@@ -123,11 +136,14 @@ impl ElvenDevice {
 }
 
 // Part 1:
-pub fn get_start_of_packet_4_unrepeated() -> Result<Option<usize>> {
+pub fn get_start_of_packet_4_unrepeated(input: impl Into<Input>) -> Result<Option<usize>> {
 
-    let broken_device = ElvenDevice::new()
+    // Read the input
+    let input = input.into().read_to_string()
     .context("getting start of packet with broken device")?;
 
+    let broken_device = ElvenDevice::new(&input);
+
     match broken_device.get_start_of_packet(4, &ElvenDevice::unrepeated_start_of_packet) {
         Some(index) => Ok(Some(index)),
         None => Ok(None)
@@ -156,13 +172,105 @@ detected?
  */
 
  // Part 2:
-pub fn get_start_of_packet_14_unrepeated() -> Result<Option<usize>> {
+pub fn get_start_of_packet_14_unrepeated(input: impl Into<Input>) -> Result<Option<usize>> {
 
-    let broken_device = ElvenDevice::new()
-    .context("getting start of packet with broken device")?;
+    // Read the input
+    let input = input.into().read_to_string()
+    .context("getting start of message with broken device")?;
+
+    let broken_device = ElvenDevice::new(&input);
 
     match broken_device.get_start_of_packet(14, &ElvenDevice::unrepeated_start_of_packet) {
         Some(index) => Ok(Some(index)),
         None => Ok(None)
     }
-}
\ No newline at end of file
+}
+
+pub struct Day6 {
+    // Number of distinct characters of the start-of-packet and start-of-message markers
+    packet_marker: usize,
+    message_marker: usize,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Self {
+            packet_marker: 4,
+            message_marker: 14,
+        }
+    }
+}
+
+impl Day6 {
+
+    pub fn packet_marker(&self) -> usize {
+        self.packet_marker
+    }
+
+    pub fn message_marker(&self) -> usize {
+        self.message_marker
+    }
+}
+
+impl Solution for Day6 {
+    type Parsed = ElvenDevice;
+    type PartOne = usize;
+    type PartTwo = usize;
+
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+    const PARAMS: &'static [&'static str] = &["packet_marker", "message_marker"];
+
+    fn configure(&mut self, params: &Params) -> Result<()> {
+
+        let packet_marker = params.usize("packet_marker", self.packet_marker)?;
+        let message_marker = params.usize("message_marker", self.message_marker)?;
+
+        self.packet_marker = packet_marker;
+        self.message_marker = message_marker;
+
+        Ok(())
+    }
+
+    fn parse(&self, input: Input) -> Result<ElvenDevice> {
+
+        // The datastream is a single line
+        Ok(ElvenDevice::new(&input.read_to_string()?))
+    }
+
+    fn part_one(&self, device: &ElvenDevice) -> Result<usize> {
+        device.get_start_of_packet(self.packet_marker, &ElvenDevice::unrepeated_start_of_packet)
+        .ok_or_else(|| AdventError::simulation(format!("No start-of-packet marker of {} characters found in datastream", self.packet_marker)).into())
+    }
+
+    fn part_two(&self, device: &ElvenDevice) -> Result<usize> {
+        device.get_start_of_packet(self.message_marker, &ElvenDevice::unrepeated_start_of_packet)
+        .ok_or_else(|| AdventError::simulation(format!("No start-of-message marker of {} characters found in datastream", self.message_marker)).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrepeated_windows_from_example() {
+        assert!(!ElvenDevice::unrepeated_start_of_packet(&['m', 'j', 'q', 'j']));
+        assert!(ElvenDevice::unrepeated_start_of_packet(&['j', 'p', 'q', 'm']));
+    }
+
+    #[test]
+    fn marker_at_the_end_of_the_datastream() {
+
+        let device = ElvenDevice::new("aaabcd\n");
+
+        assert_eq!(device.get_start_of_packet(4, &ElvenDevice::unrepeated_start_of_packet), Some(6));
+        assert_eq!(device.get_start_of_packet(14, &ElvenDevice::unrepeated_start_of_packet), None);
+    }
+
+    #[test]
+    fn empty_marker_is_never_found() {
+        assert_eq!(ElvenDevice::new("").get_start_of_packet(0, &ElvenDevice::unrepeated_start_of_packet), None);
+        assert_eq!(ElvenDevice::new("abcd").get_start_of_packet(0, &ElvenDevice::unrepeated_start_of_packet), None);
+    }
+}
@@ -0,0 +1,330 @@
+// Standard library
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+// External crates
+use anyhow::Result;
+
+// Crate modules
+use crate::general::Line;
+
+// Where in an input something went wrong. The column (1-based) and length are counted in
+// characters of the snippet, which is the offending line. The input name and line number
+// are only known by whoever reads the lines, so they are filled in later with `locate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    input: Option<String>,
+    line: Option<usize>,
+    column: usize,
+    length: usize,
+    snippet: String,
+}
+
+impl Span {
+
+    pub fn new(snippet: &str, column: usize, length: usize) -> Self {
+        Self {
+            input: None,
+            line: None,
+            column: column.max(1),
+            length: length.max(1),
+            snippet: snippet.to_string(),
+        }
+    }
+
+    // The whole snippet
+    pub fn whole(snippet: &str) -> Self {
+        Self::new(snippet, 1, snippet.chars().count())
+    }
+
+    // A part of the snippet, which must be a slice of it (as returned by `split`)
+    pub fn of(snippet: &str, part: &str) -> Self {
+
+        let offset = (part.as_ptr() as usize)
+        .saturating_sub(snippet.as_ptr() as usize)
+        .min(snippet.len());
+
+        let column = snippet.get(..offset).map_or(0, |before| before.chars().count()) + 1;
+
+        Self::new(snippet, column, part.chars().count())
+    }
+
+    // Fill in the input name and line number from the line the snippet came from
+    pub fn locate(&mut self, line: &Line) {
+        self.input = Some(line.input().to_string());
+        self.line = Some(line.number());
+    }
+
+    pub fn input(&self) -> Option<&str> {
+        self.input.as_deref()
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+// Rendered as a compiler-style diagnostic:
+//
+//  --> inputs/day_2.txt:2:3
+//   |
+// 2 | B Q
+//   |   ^
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        let input = self.input.as_deref().unwrap_or("<input>");
+
+        let line_number = self.line.map(|line| line.to_string()).unwrap_or_default();
+        let gutter = " ".repeat(line_number.len().max(1));
+
+        match self.line {
+            Some(line) => writeln!(f, "{}--> {}:{}:{}", gutter, input, line, self.column)?,
+            None => writeln!(f, "{}--> {}, column {}", gutter, input, self.column)?,
+        }
+
+        // Keep tabs before the caret, so it lines up with the snippet
+        let padding: String = self.snippet
+        .chars()
+        .take(self.column - 1)
+        .map(|character| if character == '\t' { '\t' } else { ' ' })
+        .collect();
+
+        let padding = padding + &" ".repeat((self.column - 1).saturating_sub(self.snippet.chars().count()));
+
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{:>width$} | {}", line_number, self.snippet, width = gutter.len())?;
+        write!(f, "{} | {}{}", gutter, padding, "^".repeat(self.length))
+    }
+}
+
+// Errors of reading, parsing and solving the puzzle inputs
+#[derive(Debug)]
+pub enum AdventError {
+    // The input is malformed
+    Parse { message: String, span: Option<Span> },
+    // The input is well-formed, but breaks the rules of the puzzle
+    Validation { message: String, span: Option<Span> },
+    // The input couldn't be read
+    Io { input: String, source: io::Error },
+    // Running the puzzle on the input failed
+    Simulation { message: String },
+}
+
+impl AdventError {
+
+    pub fn parse(message: impl Into<String>, span: Span) -> Self {
+        AdventError::Parse { message: message.into(), span: Some(span) }
+    }
+
+    // A parse error that can't be pinned to a line, like a missing section
+    pub fn parse_input(message: impl Into<String>) -> Self {
+        AdventError::Parse { message: message.into(), span: None }
+    }
+
+    pub fn validation(message: impl Into<String>, span: Option<Span>) -> Self {
+        AdventError::Validation { message: message.into(), span }
+    }
+
+    pub fn io(input: impl Into<String>, source: io::Error) -> Self {
+        AdventError::Io { input: input.into(), source }
+    }
+
+    pub fn simulation(message: impl Into<String>) -> Self {
+        AdventError::Simulation { message: message.into() }
+    }
+
+    pub fn span(&self) -> Option<&Span> {
+        match self {
+            AdventError::Parse { span, .. } | AdventError::Validation { span, .. } => span.as_ref(),
+            _ => None,
+        }
+    }
+
+    // Fill in the input name and line number of the span, if the error has one
+    pub fn locate(&mut self, line: &Line) {
+        if let AdventError::Parse { span: Some(span), .. } | AdventError::Validation { span: Some(span), .. } = self {
+            span.locate(line);
+        }
+    }
+}
+
+impl fmt::Display for AdventError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdventError::Parse { message, span } => {
+                write!(f, "parse error: {}", message)?;
+                span.iter().try_for_each(|span| write!(f, "\n{}", span))
+            }
+            AdventError::Validation { message, span } => {
+                write!(f, "validation error: {}", message)?;
+                span.iter().try_for_each(|span| write!(f, "\n{}", span))
+            }
+            AdventError::Io { input, .. } => write!(f, "I/O error reading '{}'", input),
+            AdventError::Simulation { message } => write!(f, "simulation error: {}", message),
+        }
+    }
+}
+
+impl Error for AdventError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AdventError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// Point the errors of parsing one line at that line
+pub trait LineContext<T> {
+    fn on_line(self, line: &Line) -> Result<T>;
+}
+
+impl<T> LineContext<T> for Result<T> {
+    fn on_line(self, line: &Line) -> Result<T> {
+        self.map_err(|mut error| {
+
+            match error.downcast_mut::<AdventError>() {
+                Some(advent_error) if advent_error.span().is_some() => advent_error.locate(line),
+                _ => {
+                    error = error.context(format!("on line {} of '{}'", line.number(), line.input()));
+                }
+            }
+
+            error
+        })
+    }
+}
+
+// How line parsers deal with a bad line
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    // Stop at the first bad line, as when solving
+    #[default]
+    FailFast,
+    // Keep going and report every bad line at once, as when validating
+    CollectAll,
+}
+
+// Every bad line of an input, found when parsing with `ParseMode::CollectAll`
+#[derive(Debug)]
+pub struct ParseErrors {
+    errors: Vec<anyhow::Error>,
+}
+
+impl ParseErrors {
+
+    pub fn errors(&self) -> &[anyhow::Error] {
+        &self.errors
+    }
+
+    pub fn into_errors(self) -> Vec<anyhow::Error> {
+        self.errors
+    }
+}
+
+impl fmt::Display for ParseErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+
+        write!(f, "{} malformed line(s)", self.errors.len())?;
+
+        self.errors.iter().try_for_each(|error| write!(f, "\n\n{:#}", error))
+    }
+}
+
+impl Error for ParseErrors {}
+
+// Parse the lines of an input one at a time with `parse_line`, which may skip a line by
+// returning `None`. Errors are pointed at their line. Failing to read a line always stops.
+pub fn parse_lines<L, T>(
+    lines: impl IntoIterator<Item = Result<L>>,
+    mode: ParseMode,
+    mut parse_line: impl FnMut(&Line) -> Result<Option<T>>
+) -> Result<Vec<T>>
+where
+    L: Borrow<Line>
+{
+
+    let mut parsed: Vec<T> = Vec::new();
+    let mut errors: Vec<anyhow::Error> = Vec::new();
+
+    for line in lines {
+
+        let line = line?;
+        let line = line.borrow();
+
+        match parse_line(line).on_line(line) {
+            Result::Ok(Some(item)) => parsed.push(item),
+            Result::Ok(None) => (),
+            Result::Err(error) if mode == ParseMode::CollectAll => errors.push(error),
+            Result::Err(error) => return Err(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(parsed)
+    } else {
+        Err(ParseErrors { errors }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn span_of_a_part() {
+
+        let line = "2-4,6-x";
+        let parts: Vec<&str> = line.split(&[',', '-'][..]).collect();
+
+        let span = Span::of(line, parts[3]);
+
+        assert_eq!((span.column(), span.length()), (7, 1));
+    }
+
+    #[test]
+    fn located_diagnostic() {
+
+        let error: Result<()> = Err(AdventError::parse("unknown encrypted move 'Q'", Span::new("B Q", 3, 1)).into());
+
+        let error = error.on_line(&Line::new("day_2.txt", 12, "B Q")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "parse error: unknown encrypted move 'Q'\n  --> day_2.txt:12:3\n   |\n12 | B Q\n   |   ^"
+        );
+    }
+
+    #[test]
+    fn collect_all_keeps_going() {
+
+        let lines = ["1", "x", "3", "y"].map(|text| Line::new("<text>", 1, text));
+
+        let parse_number = |line: &Line| -> Result<Option<i32>> {
+            line.text().parse::<i32>()
+            .map(Some)
+            .map_err(|_| AdventError::parse("not a number", Span::whole(line.text())).into())
+        };
+
+        let error = parse_lines(lines.iter().map(Ok), ParseMode::FailFast, parse_number).unwrap_err();
+        assert!(error.downcast_ref::<AdventError>().is_some());
+
+        let error = parse_lines(lines.iter().map(Ok), ParseMode::CollectAll, parse_number).unwrap_err();
+        assert_eq!(error.downcast_ref::<ParseErrors>().map(|errors| errors.errors().len()), Some(2));
+    }
+}
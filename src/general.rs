@@ -1,48 +1,763 @@
 // Standard Library
-use std::fs::File;
-use std::path::Path;
-use std::io::Read;
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::sync::{Arc, Mutex, OnceLock};
 
 // External crates
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
 
-// Read a text input file to a string
-pub fn read_text_input(path: &str) -> Result<String> {
+// Crate modules
+use crate::error::AdventError;
+
+// Environment variable pointing to a directory with the puzzle inputs
+pub const INPUTS_DIR_VAR: &str = "ADVENT_INPUTS_DIR";
+
+// Project configuration file, looked up in the working directory
+pub const CONFIG_FILE: &str = "advent.toml";
+
+// Conventional inputs directory, relative to the working directory
+pub const DEFAULT_INPUTS_DIR: &str = "inputs";
+
+// Read a text input file to a string, decompressing it if needed
+pub fn read_text_input<P: AsRef<Path>>(path: P) -> Result<String> {
 
     // Convert to path, and check if exists
-    let path_inner = Path::new(path);
-    
+    let path_inner = path.as_ref();
+    let io_error = |error| AdventError::io(path_inner.display().to_string(), error);
+
     if path_inner.try_exists()
-    .context("reading text input file")? {
+    .map_err(io_error)? {
 
-        let mut file = File::open(path_inner)
-        .context("reading text input file")?;
+        let mut file = open_text_file(path_inner)?;
 
         let mut input_string = String::new();
 
         file.read_to_string(&mut input_string)
-        .context("reading text input file")?;
+        .map_err(io_error)?;
 
         Ok(input_string)
 
     } else {
 
-        Err(anyhow!("File '{}' does not exist", path))
+        Err(io_error(io::Error::new(io::ErrorKind::NotFound, "file does not exist")).into())
+    }
+}
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+// How an input file is compressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Gzip,
+    Zstd,
+}
+
+// By the file's extension or, failing that, its first bytes
+fn detect_compression(path: &Path, first_bytes: &[u8]) -> Compression {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("gz") => Compression::Gzip,
+        Some("zst") => Compression::Zstd,
+        _ if first_bytes.starts_with(&GZIP_MAGIC) => Compression::Gzip,
+        _ if first_bytes.starts_with(&ZSTD_MAGIC) => Compression::Zstd,
+        _ => Compression::None,
+    }
+}
+
+// Open a text file for buffered reading. Compressed files (with the `compressed` feature)
+// are decompressed as they are read, so they are never held in memory whole.
+fn open_text_file(path: &Path) -> Result<Box<dyn BufRead + Send>> {
+
+    let io_error = |error| AdventError::io(path.display().to_string(), error);
+
+    let mut file = BufReader::new(File::open(path).map_err(io_error)?);
+
+    // Peek at the first bytes, which are still read afterwards
+    let compression = detect_compression(path, file.fill_buf().map_err(io_error)?);
+
+    match compression {
+        Compression::None => Ok(Box::new(file)),
+        #[cfg(feature = "compressed")]
+        Compression::Gzip => Ok(Box::new(BufReader::new(flate2::bufread::MultiGzDecoder::new(file)))),
+        #[cfg(feature = "compressed")]
+        Compression::Zstd => {
+
+            let decoder = zstd::stream::read::Decoder::with_buffer(file)
+            .map_err(io_error)?;
+
+            Ok(Box::new(BufReader::new(decoder)))
+        }
+        #[cfg(not(feature = "compressed"))]
+        compression => Err(io_error(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("{:?} compressed input, which needs the 'compressed' feature", compression),
+        )).into()),
     }
 }
 
-pub fn get_input_lines(path: &str) -> Result<Vec<String>> {
+// Where a puzzle input comes from
+pub enum Input {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+    // Text already read from another input, keeping that input's name for messages
+    Named(String, String),
+    Reader(Box<dyn Read + Send>),
+}
 
-    // Get input file as a string
-    let input_string = read_text_input(path)
-    .context("getting input lines")?;
+impl Input {
 
-    // Turn lines into vector of Strings
-    let mut input_lines: Vec<String> = Vec::new();
+    // A command line argument: '-' is stdin, anything else a file path
+    pub fn from_arg(arg: &str) -> Self {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::Path(PathBuf::from(arg))
+        }
+    }
 
-    for line in input_string.lines() {
-        input_lines.push(line.to_string());
+    pub fn reader<R: Read + Send + 'static>(reader: R) -> Self {
+        Input::Reader(Box::new(reader))
     }
 
-    Ok(input_lines)
+    // Name of the input for messages and reports
+    pub fn name(&self) -> String {
+        match self {
+            Input::Path(path) => path.display().to_string(),
+            Input::Stdin => "<stdin>".to_string(),
+            Input::Text(_) => "<text>".to_string(),
+            Input::Named(name, _) => name.clone(),
+            Input::Reader(_) => "<reader>".to_string(),
+        }
+    }
+
+    // Open the input for buffered reading, without reading it yet
+    pub fn open(self) -> Result<Box<dyn BufRead + Send>> {
+
+        let reader: Box<dyn BufRead + Send> = match self {
+            Input::Path(path) => open_text_file(&path)?,
+            Input::Stdin => Box::new(BufReader::new(io::stdin())),
+            Input::Text(text) | Input::Named(_, text) => Box::new(Cursor::new(text.into_bytes())),
+            Input::Reader(reader) => Box::new(BufReader::new(reader)),
+        };
+
+        Ok(reader)
+    }
+
+    pub fn read_to_string(self) -> Result<String> {
+
+        let mut input_string = String::new();
+
+        match self {
+            Input::Path(path) => return read_text_input(path),
+            Input::Text(text) | Input::Named(_, text) => return Ok(text),
+            Input::Stdin => {
+                io::stdin().read_to_string(&mut input_string)
+                .map_err(|error| AdventError::io("<stdin>", error))?;
+            }
+            Input::Reader(mut reader) => {
+                reader.read_to_string(&mut input_string)
+                .map_err(|error| AdventError::io("<reader>", error))?;
+            }
+        }
+
+        Ok(input_string)
+    }
+}
+
+impl fmt::Debug for Input {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Input({})", self.name())
+    }
+}
+
+// Strings are the input text itself; paths are files to read
+impl From<&str> for Input {
+    fn from(text: &str) -> Self {
+        Input::Text(text.to_string())
+    }
+}
+
+impl From<String> for Input {
+    fn from(text: String) -> Self {
+        Input::Text(text)
+    }
+}
+
+impl From<&Path> for Input {
+    fn from(path: &Path) -> Self {
+        Input::Path(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(path: PathBuf) -> Self {
+        Input::Path(path)
+    }
+}
+
+// A line of an input, with the input's name and its 1-based line number
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Line {
+    input: Arc<str>,
+    number: usize,
+    text: String,
+}
+
+impl Line {
+
+    pub fn new(input: &str, number: usize, text: &str) -> Self {
+        Self {
+            input: Arc::from(input),
+            number,
+            text: text.to_string(),
+        }
+    }
+
+    // Name of the input the line was read from
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn number(&self) -> usize {
+        self.number
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+// Reads an input one line at a time, keeping track of line numbers. Line endings
+// ('\n' or '\r\n') are not included in the lines.
+pub struct LineReader {
+    reader: Box<dyn BufRead + Send>,
+    name: Arc<str>,
+    line_number: usize,
+}
+
+impl LineReader {
+
+    pub fn new(input: Input) -> Result<Self> {
+
+        let name = input.name();
+
+        let reader = input.open()?;
+
+        Ok(
+            Self {
+                reader,
+                name: Arc::from(name),
+                line_number: 0
+            }
+        )
+    }
+
+    // Name of the input being read
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Number of the last line read
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+impl Iterator for LineReader {
+    type Item = Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+
+        let mut text = String::new();
+
+        match self.reader.read_line(&mut text) {
+            Result::Ok(0) => None,
+            Result::Ok(_) => {
+
+                self.line_number += 1;
+
+                if text.ends_with('\n') {
+                    text.pop();
+
+                    if text.ends_with('\r') {
+                        text.pop();
+                    }
+                }
+
+                Some(Ok(Line { input: self.name.clone(), number: self.line_number, text }))
+            }
+            Result::Err(error) => Some(
+                Err(AdventError::io(self.name.as_ref(), error))
+                .with_context(|| format!("reading line {}", self.line_number + 1))
+            ),
+        }
+    }
+}
+
+// Read all the lines of an input
+pub fn read_lines(input: Input) -> Result<Vec<Line>> {
+    LineReader::new(input)?.collect()
+}
+
+// Parsed inputs kept in memory at most, the least recently parsed going first
+const CACHED_INPUTS: usize = 32;
+
+// Part of every key, since entries saved by another version may not load, or worse, load
+// into something else
+const CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// SHA-256 of an input's content and of how it was parsed. Unlike a short or fast hash,
+// another input can't be crafted to share it, so a key is enough to tell inputs apart,
+// here and in the cache directory.
+type CacheKey = [u8; 32];
+
+// Parsed inputs, keyed by a hash of the crate version, what was parsed (like 'day_5'), the
+// input's name and its content, so an input is parsed once per process however many parts
+// or days use it. A file is streamed through the hash, and read again only if it has to
+// be parsed; text already in memory is hashed as it is. Stdin and readers can only be read
+// once, so they are always parsed.
+//
+// With a directory, parsed files are also saved there as JSON, named by their key, and
+// loaded back by later runs instead of parsing very large inputs again. Text is only kept
+// in memory.
+#[derive(Default)]
+pub struct ParseCache {
+    entries: Mutex<VecDeque<(CacheKey, Arc<dyn Any + Send + Sync>)>>,
+    dir: Mutex<Option<PathBuf>>,
+}
+
+impl ParseCache {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_dir<P: AsRef<Path>>(dir: P) -> Self {
+        let cache = Self::new();
+        cache.set_dir(Some(dir.as_ref().to_path_buf()));
+        cache
+    }
+
+    // The cache shared by the whole process
+    pub fn global() -> &'static ParseCache {
+        static CACHE: OnceLock<ParseCache> = OnceLock::new();
+        CACHE.get_or_init(ParseCache::new)
+    }
+
+    pub fn set_dir(&self, dir: Option<PathBuf>) {
+        *self.dir.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = dir;
+    }
+
+    // Return the input's parsed form from memory, from the cache directory, or by parsing it
+    pub fn get_or_parse<T, F>(&self, kind: &str, input: Input, parse: F) -> Result<Arc<T>>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + 'static,
+        F: FnOnce(Input) -> Result<T>,
+    {
+
+        let name = input.name();
+
+        let key = match &input {
+            Input::Path(path) => match File::open(path).and_then(|mut file| cache_key(kind, &name, &mut file)) {
+                Result::Ok(key) => key,
+                // Parsing fails the way it should, if the file can't be read
+                Result::Err(_) => return parse(input).map(Arc::new),
+            },
+            Input::Text(text) | Input::Named(_, text) => cache_key(kind, &name, &mut text.as_bytes())?,
+            Input::Stdin | Input::Reader(_) => return parse(input).map(Arc::new),
+        };
+
+        let saved = matches!(input, Input::Path(_));
+
+        if let Some(parsed) = self.get(key) {
+            debug!(kind, input = %name, "parsed input found in memory");
+            return Ok(parsed)
+        }
+
+        let path = self.dir
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .as_ref()
+        .filter(|_| saved)
+        .map(|dir| dir.join(format!("{}-{}.json", kind, hex(&key))));
+
+        if let Some(parsed) = path.as_deref().and_then(load_parsed::<T>) {
+            debug!(kind, input = %name, "parsed input loaded from cache directory");
+            return Ok(self.insert(key, parsed))
+        }
+
+        let parsed = parse(input)?;
+
+        if let Some(path) = &path {
+            if let Result::Err(error) = save_parsed(path, &parsed) {
+                warn!("Couldn't save parsed input to the cache: {:#}", error);
+            }
+        }
+
+        Ok(self.insert(key, parsed))
+    }
+
+    fn get<T: Send + Sync + 'static>(&self, key: CacheKey) -> Option<Arc<T>> {
+        self.entries
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .iter()
+        .find(|(entry_key, _)| *entry_key == key)
+        .and_then(|(_, parsed)| parsed.clone().downcast::<T>().ok())
+    }
+
+    fn insert<T: Send + Sync + 'static>(&self, key: CacheKey, parsed: T) -> Arc<T> {
+
+        let parsed = Arc::new(parsed);
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        entries.retain(|(entry_key, _)| *entry_key != key);
+
+        if entries.len() == CACHED_INPUTS {
+            entries.pop_front();
+        }
+
+        entries.push_back((key, parsed.clone()));
+
+        parsed
+    }
+}
+
+// The names end in a byte that's never in UTF-8, so ("ab", "c") and ("a", "bc") differ.
+// The content is streamed, so a file is never held in memory whole.
+fn cache_key(kind: &str, name: &str, content: &mut dyn Read) -> io::Result<CacheKey> {
+
+    let mut hasher = Sha256::new();
+
+    for part in [CACHE_VERSION, kind, name] {
+        hasher.update(part.as_bytes());
+        hasher.update([0xff]);
+    }
+
+    io::copy(content, &mut hasher)?;
+
+    Ok(hasher.finalize().into())
+}
+
+fn hex(key: &CacheKey) -> String {
+    key.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+// A saved entry that can't be read is parsed again, and saved over
+fn load_parsed<T: DeserializeOwned>(path: &Path) -> Option<T> {
+
+    let json = fs::read_to_string(path).ok()?;
+
+    match serde_json::from_str(&json) {
+        Result::Ok(parsed) => Some(parsed),
+        Result::Err(error) => {
+            warn!("Ignoring unreadable cache entry '{}': {}", path.display(), error);
+            None
+        }
+    }
+}
+
+fn save_parsed<T: Serialize>(path: &Path, parsed: &T) -> Result<()> {
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+        .with_context(|| format!("creating cache directory '{}'", dir.display()))?;
+    }
+
+    let json = serde_json::to_string(parsed)?;
+
+    fs::write(path, json)
+    .with_context(|| format!("writing cache entry '{}'", path.display()))
+}
+
+// The [inputs] table of the configuration file:
+//
+// [inputs]
+// dir = "/home/me/advent_inputs"
+// day_5 = "/home/me/crates.txt"
+#[derive(Debug, Default, Deserialize)]
+pub struct InputsConfig {
+    dir: Option<PathBuf>,
+    #[serde(flatten)]
+    days: HashMap<String, PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    inputs: InputsConfig,
+}
+
+impl InputsConfig {
+
+    // Load the [inputs] table from a configuration file, if the file exists
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+
+        let path = path.as_ref();
+
+        if !path.try_exists().context("loading configuration file")? {
+            return Ok(Self::default())
+        }
+
+        let config_string = read_text_input(path)
+        .context("loading configuration file")?;
+
+        let config: ConfigFile = toml::from_str(&config_string)
+        .with_context(|| format!("parsing configuration file '{}'", path.display()))?;
+
+        Ok(config.inputs)
+    }
+}
+
+// Finds the input file of each day. In order of precedence:
+//
+// 1. An explicit file given on the command line ('-' for stdin)
+// 2. An inputs directory given on the command line
+// 3. The ADVENT_INPUTS_DIR environment variable
+// 4. A per-day file, or an inputs directory, in advent.toml
+// 5. The conventional inputs/day_N.txt
+#[derive(Debug, Default)]
+pub struct InputResolver {
+    input_file: Option<PathBuf>,
+    inputs_dir: Option<PathBuf>,
+    env_dir: Option<PathBuf>,
+    config: InputsConfig,
+}
+
+impl InputResolver {
+
+    pub fn new(input_file: Option<PathBuf>, inputs_dir: Option<PathBuf>) -> Result<Self> {
+
+        let env_dir = env::var_os(INPUTS_DIR_VAR).map(PathBuf::from);
+
+        let config = InputsConfig::load(CONFIG_FILE)
+        .context("creating input resolver")?;
+
+        Ok(
+            Self {
+                input_file,
+                inputs_dir,
+                env_dir,
+                config
+            }
+        )
+    }
+
+    // The input of a day, which is stdin if the explicit file is '-'
+    pub fn input(&self, day: u8) -> Input {
+        match &self.input_file {
+            Some(input_file) if input_file.as_os_str() == "-" => Input::Stdin,
+            _ => Input::Path(self.resolve(day)),
+        }
+    }
+
+    pub fn resolve(&self, day: u8) -> PathBuf {
+
+        let file_name = format!("day_{}.txt", day);
+
+        if let Some(input_file) = &self.input_file {
+            return input_file.clone()
+        }
+
+        if let Some(inputs_dir) = &self.inputs_dir {
+            return dir_input(inputs_dir, &file_name)
+        }
+
+        if let Some(env_dir) = &self.env_dir {
+            return dir_input(env_dir, &file_name)
+        }
+
+        if let Some(day_file) = self.config.days.get(&format!("day_{}", day)) {
+            return day_file.clone()
+        }
+
+        if let Some(config_dir) = &self.config.dir {
+            return dir_input(config_dir, &file_name)
+        }
+
+        dir_input(Path::new(DEFAULT_INPUTS_DIR), &file_name)
+    }
+
+    // Resolve and read the input of a day
+    pub fn read(&self, day: u8) -> Result<String> {
+
+        self.input(day)
+        .read_to_string()
+        .with_context(|| format!("reading input of Day {}", day))
+    }
+}
+
+// An input file in a directory, or its compressed version if only that one exists
+fn dir_input(dir: &Path, file_name: &str) -> PathBuf {
+
+    let path = dir.join(file_name);
+
+    if path.exists() {
+        return path
+    }
+
+    ["gz", "zst"]
+    .iter()
+    .map(|extension| dir.join(format!("{}.{}", file_name, extension)))
+    .find(|compressed_path| compressed_path.exists())
+    .unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn input_from_arg() {
+        assert!(matches!(Input::from_arg("-"), Input::Stdin));
+        assert!(matches!(Input::from_arg("inputs/day_1.txt"), Input::Path(_)));
+    }
+
+    #[test]
+    fn input_to_string() {
+        assert_eq!(Input::from("A Y\n").read_to_string().unwrap(), "A Y\n");
+        assert_eq!(Input::reader("B X\n".as_bytes()).read_to_string().unwrap(), "B X\n");
+    }
+
+    #[test]
+    fn line_reader_numbers_lines() {
+
+        let lines = read_lines(Input::from("move 1 from 2 to 1\r\n\nmove 3 from 1 to 3")).unwrap();
+
+        assert_eq!(lines, vec![
+            Line::new("<text>", 1, "move 1 from 2 to 1"),
+            Line::new("<text>", 2, ""),
+            Line::new("<text>", 3, "move 3 from 1 to 3"),
+        ]);
+    }
+
+    #[test]
+    fn parse_cache_parses_once() {
+
+        let cache = ParseCache::new();
+        let mut parses = 0;
+
+        let mut parse = |text: &str| cache.get_or_parse("lines", Input::from(text), |input| {
+            parses += 1;
+            read_lines(input)
+        });
+
+        let first = parse("2-4,6-8\n").unwrap();
+        let again = parse("2-4,6-8\n").unwrap();
+        parse("2-3,4-5\n").unwrap();
+
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(parses, 2);
+
+        // The same content under another kind is another entry
+        assert!(cache.get_or_parse("other", Input::from("2-4,6-8\n"), |_| Ok(0)).is_ok());
+    }
+
+    #[test]
+    fn parse_cache_saves_to_dir() {
+
+        let dir = TempDir::new("cache");
+        let input_path = dir.join("day_4.txt");
+        let input = || Input::Path(input_path.clone());
+
+        fs::write(&input_path, "2-4,6-8\n").unwrap();
+
+        let parsed = ParseCache::with_dir(dir.join("cache")).get_or_parse("lines", input(), read_lines).unwrap();
+
+        // A new cache, as in a later run, finds the saved entry without parsing
+        let loaded: Arc<Vec<Line>> = ParseCache::with_dir(dir.join("cache"))
+        .get_or_parse("lines", input(), |_| panic!("parsed again"))
+        .unwrap();
+
+        assert_eq!(loaded, parsed);
+
+        // Until its content changes, even keeping its size and modification time
+        let modified = fs::metadata(&input_path).unwrap().modified().unwrap();
+
+        fs::write(&input_path, "2-4,6-9\n").unwrap();
+        File::options().write(true).open(&input_path).unwrap().set_modified(modified).unwrap();
+
+        let changed: Arc<Vec<Line>> = ParseCache::with_dir(dir.join("cache")).get_or_parse("lines", input(), read_lines).unwrap();
+        assert_eq!(changed[0].text(), "2-4,6-9");
+
+        // Text is never saved
+        ParseCache::with_dir(dir.join("cache")).get_or_parse("lines", Input::from("1-2,3-4\n"), read_lines).unwrap();
+        assert_eq!(fs::read_dir(dir.join("cache")).unwrap().count(), 2);
+    }
+
+    #[cfg(feature = "compressed")]
+    #[test]
+    fn compressed_inputs() {
+
+        use std::io::Write;
+
+        let dir = TempDir::new("compressed");
+        let text = "2-4,6-8\n2-8,3-7\n";
+
+        let mut gzip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        gzip.write_all(text.as_bytes()).unwrap();
+        fs::write(dir.join("day_4.txt.gz"), gzip.finish().unwrap()).unwrap();
+
+        // Without the extension, it's found by its magic bytes
+        fs::write(dir.join("day_4.bin"), zstd::encode_all(text.as_bytes(), 0).unwrap()).unwrap();
+
+        assert_eq!(read_text_input(dir.join("day_4.txt.gz")).unwrap(), text);
+        assert_eq!(read_lines(Input::Path(dir.join("day_4.bin"))).unwrap()[1].text(), "2-8,3-7");
+    }
+
+    #[cfg(not(feature = "compressed"))]
+    #[test]
+    fn compressed_inputs_need_feature() {
+
+        let dir = TempDir::new("compressed");
+        fs::write(dir.join("day_4.txt"), [0x1f, 0x8b, 0x08, 0x00]).unwrap();
+
+        let error = read_text_input(dir.join("day_4.txt")).unwrap_err();
+        assert!(format!("{:#}", error).contains("needs the 'compressed' feature"));
+    }
+
+    #[test]
+    fn resolver_finds_compressed_inputs() {
+
+        let dir = TempDir::new("resolver");
+        fs::write(dir.join("day_5.txt.zst"), "").unwrap();
+
+        let resolver = InputResolver { inputs_dir: Some(dir.to_path_buf()), ..Default::default() };
+
+        assert_eq!(resolver.resolve(5), dir.join("day_5.txt.zst"));
+        assert_eq!(resolver.resolve(6), dir.join("day_6.txt"));
+    }
+
+    #[test]
+    fn resolver_precedence() {
+
+        let resolver = InputResolver {
+            inputs_dir: Some(PathBuf::from("cli_dir")),
+            env_dir: Some(PathBuf::from("env_dir")),
+            ..Default::default()
+        };
+
+        assert_eq!(resolver.resolve(3), Path::new("cli_dir/day_3.txt"));
+
+        let resolver = InputResolver {
+            config: toml::from_str::<ConfigFile>("[inputs]\ndir = \"config_dir\"\nday_5 = \"crates.txt\"").unwrap().inputs,
+            ..Default::default()
+        };
+
+        assert_eq!(resolver.resolve(5), Path::new("crates.txt"));
+        assert_eq!(resolver.resolve(6), Path::new("config_dir/day_6.txt"));
+        assert_eq!(InputResolver::default().resolve(1), Path::new("inputs/day_1.txt"));
+    }
 }
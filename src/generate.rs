@@ -0,0 +1,398 @@
+// Standard library
+use std::fmt::Write;
+
+// External crates
+use anyhow::{Result, anyhow};
+
+// Item types of day 3, in priority order
+const ITEM_TYPES: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+// Small seeded random number generator (SplitMix64), so a seed gives the same input on
+// every platform and every version of the crate
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    // A number between `low` and `high`, both included
+    pub fn between(&mut self, low: usize, high: usize) -> usize {
+        low + (self.next_u64() % (high - low + 1) as u64) as usize
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.between(0, items.len() - 1)]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            items.swap(index, self.between(0, index));
+        }
+    }
+}
+
+// A generated input, with the answers it should give
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part_one: String,
+    pub part_two: String,
+}
+
+// Generate a valid input for a day. The size is the number of Elves (day 1), rounds
+// (day 2), Elf groups (day 3), assignment pairs (day 4), moves (day 5) or characters of
+// the datastream (day 6).
+pub fn generate(day: u8, size: usize, seed: u64) -> Result<Generated> {
+
+    let mut rng = Rng::new(seed);
+
+    match day {
+        1 => Ok(calorie_groups(&mut rng, size)),
+        2 => Ok(strategy_guide(&mut rng, size)),
+        3 => Ok(rucksack_groups(&mut rng, size)),
+        4 => Ok(assignment_pairs(&mut rng, size)),
+        5 => Ok(crate_procedure(&mut rng, size)),
+        6 => Ok(datastream(&mut rng, size)),
+        _ => Err(anyhow!("There is no generator for Day {}", day)),
+    }
+}
+
+// Day 1: groups of Calories, one group per Elf
+fn calorie_groups(rng: &mut Rng, elves: usize) -> Generated {
+
+    let mut groups: Vec<String> = Vec::new();
+    let mut totals: Vec<usize> = Vec::new();
+
+    for _ in 0..elves.max(1) {
+
+        let items: Vec<usize> = (0..rng.between(1, 15)).map(|_| rng.between(1000, 60000)).collect();
+
+        totals.push(items.iter().sum());
+        groups.push(items.iter().map(|item| format!("{}\n", item)).collect());
+    }
+
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+
+    Generated {
+        input: groups.join("\n"),
+        part_one: totals[0].to_string(),
+        part_two: totals.iter().take(3).sum::<usize>().to_string(),
+    }
+}
+
+// Day 2: rounds of 'A-C X-Z'. Shapes and outcomes are scored as numbers modulo 3.
+fn strategy_guide(rng: &mut Rng, rounds: usize) -> Generated {
+
+    let mut input = String::new();
+    let mut score_one = 0;
+    let mut score_two = 0;
+
+    for _ in 0..rounds {
+
+        let opponent = rng.between(0, 2);
+        let column = rng.between(0, 2);
+
+        writeln!(input, "{} {}", (b'A' + opponent as u8) as char, (b'X' + column as u8) as char).unwrap();
+
+        // Part 1: the column is a shape; 0 is a loss, 1 a draw and 2 a win
+        let outcome = (column + 4 - opponent) % 3;
+        score_one += column + 1 + outcome * 3;
+
+        // Part 2: the column is the outcome
+        let shape = (opponent + column + 2) % 3;
+        score_two += shape + 1 + column * 3;
+    }
+
+    Generated {
+        input,
+        part_one: score_one.to_string(),
+        part_two: score_two.to_string(),
+    }
+}
+
+fn priority(item: char) -> usize {
+    ITEM_TYPES.find(item).map_or(0, |index| index + 1)
+}
+
+// Day 3: groups of three rucksacks. Each rucksack takes its items from its own third of
+// the item types, plus the group's badge, so the badge is the only item the three share.
+// Only the wrong item is in both compartments.
+fn rucksack_groups(rng: &mut Rng, groups: usize) -> Generated {
+
+    let item_types: Vec<char> = ITEM_TYPES.chars().collect();
+
+    let mut input = String::new();
+    let mut priorities_one = 0;
+    let mut priorities_two = 0;
+
+    // Part 2 needs a whole group
+    for _ in 0..groups.max(1) {
+
+        let badge = rng.pick(&item_types);
+
+        let mut others: Vec<char> = item_types.iter().copied().filter(|item| *item != badge).collect();
+        rng.shuffle(&mut others);
+
+        for pool in others.chunks(others.len() / 3) {
+
+            let mut items: Vec<char> = pool.to_vec();
+            items.push(badge);
+            rng.shuffle(&mut items);
+
+            // The wrong item, then the items of each compartment
+            let wrong_item = items[0];
+            let (first, second) = items[1..].split_at(items.len() / 2);
+
+            let size = rng.between(2, 16);
+
+            let mut compartments = [first, second].map(|own_items| {
+
+                let mut compartment = vec![wrong_item];
+
+                if own_items.contains(&badge) {
+                    compartment.push(badge);
+                }
+
+                while compartment.len() < size {
+                    compartment.push(rng.pick(own_items));
+                }
+
+                compartment
+            });
+
+            compartments.iter_mut().for_each(|compartment| rng.shuffle(compartment));
+
+            writeln!(input, "{}", compartments.concat().iter().collect::<String>()).unwrap();
+
+            priorities_one += priority(wrong_item);
+        }
+
+        priorities_two += priority(badge);
+    }
+
+    Generated {
+        input,
+        part_one: priorities_one.to_string(),
+        part_two: priorities_two.to_string(),
+    }
+}
+
+// Day 4: pairs of section ranges
+fn assignment_pairs(rng: &mut Rng, pairs: usize) -> Generated {
+
+    let mut input = String::new();
+    let mut full = 0;
+    let mut partial = 0;
+
+    for _ in 0..pairs {
+
+        let mut range = || {
+            let start = rng.between(1, 99);
+            (start, rng.between(start, 99))
+        };
+
+        let (first, second) = (range(), range());
+
+        writeln!(input, "{}-{},{}-{}", first.0, first.1, second.0, second.1).unwrap();
+
+        if (first.0 <= second.0 && second.1 <= first.1) || (second.0 <= first.0 && first.1 <= second.1) {
+            full += 1;
+        }
+
+        if first.0 <= second.1 && second.0 <= first.1 {
+            partial += 1;
+        }
+    }
+
+    Generated {
+        input,
+        part_one: full.to_string(),
+        part_two: partial.to_string(),
+    }
+}
+
+// Day 5: a crate drawing, then moves that never take more crates than a stack holds
+fn crate_procedure(rng: &mut Rng, moves: usize) -> Generated {
+
+    // The stack tags line is recognized by its " 1   2  " start, so there are at least 3 stacks
+    let num_stacks = rng.between(3, 9);
+
+    let stacks: Vec<Vec<char>> = (0..num_stacks)
+    .map(|_| (0..rng.between(0, 8)).map(|_| (b'A' + rng.between(0, 25) as u8) as char).collect())
+    .collect();
+
+    let mut input = String::new();
+
+    // The drawing, from the highest row down, with every row as wide as the tags line
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+
+    for row in (0..height).rev() {
+
+        let cells: Vec<String> = stacks
+        .iter()
+        .map(|stack| stack.get(row).map_or("   ".to_string(), |tag| format!("[{}]", tag)))
+        .collect();
+
+        writeln!(input, "{}", cells.join(" ")).unwrap();
+    }
+
+    let tags: Vec<String> = (1..=num_stacks).map(|tag| format!(" {} ", tag)).collect();
+    writeln!(input, "{}\n", tags.join(" ")).unwrap();
+
+    // Both cranes move the same number of crates, so the stack heights stay in step
+    let mut crate_mover_9000 = stacks.clone();
+    let mut crate_mover_9001 = stacks;
+
+    for _ in 0..moves {
+
+        let non_empty: Vec<usize> = (0..num_stacks).filter(|stack| !crate_mover_9000[*stack].is_empty()).collect();
+
+        if non_empty.is_empty() {
+            break
+        }
+
+        let origin = rng.pick(&non_empty);
+        let destination = (origin + rng.between(1, num_stacks - 1)) % num_stacks;
+        let number = rng.between(1, crate_mover_9000[origin].len());
+
+        writeln!(input, "move {} from {} to {}", number, origin + 1, destination + 1).unwrap();
+
+        for _ in 0..number {
+            let moved = crate_mover_9000[origin].pop().unwrap();
+            crate_mover_9000[destination].push(moved);
+        }
+
+        let remaining = crate_mover_9001[origin].len() - number;
+        let moved = crate_mover_9001[origin].split_off(remaining);
+        crate_mover_9001[destination].extend(moved);
+    }
+
+    let tops = |stacks: &[Vec<char>]| -> String { stacks.iter().filter_map(|stack| stack.last()).collect() };
+
+    Generated {
+        input,
+        part_one: tops(&crate_mover_9000),
+        part_two: tops(&crate_mover_9001),
+    }
+}
+
+// Day 6: a datastream with a start-of-packet and a start-of-message marker planted at
+// known positions. Before each marker, every window repeats a character: the stream
+// first uses only 3 letters, then only 13, and the character right before each marker is
+// the marker's first one.
+fn datastream(rng: &mut Rng, length: usize) -> Generated {
+
+    let length = length.max(20);
+
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    rng.shuffle(&mut letters);
+
+    let mut stream: Vec<char> = Vec::new();
+
+    // Up to the start-of-packet marker
+    for _ in 0..rng.between(1, length / 3) {
+        stream.push(rng.pick(&letters[..3]));
+    }
+
+    let mut packet_marker = letters[1..7].to_vec();
+    rng.shuffle(&mut packet_marker);
+    packet_marker.retain(|letter| letter != stream.last().unwrap());
+    packet_marker.insert(0, *stream.last().unwrap());
+    stream.extend(&packet_marker[..4]);
+
+    let start_of_packet = stream.len();
+
+    // Up to the start-of-message marker
+    for _ in 0..rng.between(1, length / 3) {
+        stream.push(rng.pick(&letters[..13]));
+    }
+
+    let first = *stream.last().unwrap();
+    let mut message_marker: Vec<char> = letters.iter().copied().filter(|letter| *letter != first).collect();
+    rng.shuffle(&mut message_marker);
+    stream.push(first);
+    stream.extend(&message_marker[..13]);
+
+    let start_of_message = stream.len();
+
+    while stream.len() < length {
+        stream.push(rng.pick(&letters));
+    }
+
+    Generated {
+        input: stream.iter().collect::<String>() + "\n",
+        part_one: start_of_packet.to_string(),
+        part_two: start_of_message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::general::Input;
+    use crate::solution::{Part, find_solver};
+
+    #[test]
+    fn generated_inputs_give_the_expected_answers() {
+
+        for day in 1..=6 {
+            for seed in 0..20 {
+
+                let generated = generate(day, 60, seed).unwrap();
+
+                let answers: Vec<String> = find_solver(day)
+                .unwrap()
+                .run(Input::from(generated.input.as_str()), &Part::ALL)
+                .unwrap()
+                .into_iter()
+                .map(|answer| answer.unwrap().to_string())
+                .collect();
+
+                assert_eq!(answers, vec![generated.part_one, generated.part_two], "Day {} seed {}", day, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn smallest_inputs_give_the_expected_answers() {
+
+        for day in 1..=6 {
+            for size in 0..=1 {
+
+                let generated = generate(day, size, 0).unwrap();
+
+                let answers: Vec<String> = find_solver(day)
+                .unwrap()
+                .run(Input::from(generated.input.as_str()), &Part::ALL)
+                .unwrap()
+                .into_iter()
+                .map(|answer| answer.unwrap().to_string())
+                .collect();
+
+                assert_eq!(answers, vec![generated.part_one, generated.part_two], "Day {} size {}", day, size);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        assert_eq!(generate(5, 100, 7).unwrap(), generate(5, 100, 7).unwrap());
+        assert_ne!(generate(5, 100, 7).unwrap().input, generate(5, 100, 8).unwrap().input);
+    }
+}
@@ -0,0 +1,31 @@
+//! Advent of Code 2022 solutions, as a library.
+//!
+//! Each `day_N` module exposes its puzzle types (like `RPSMove`, `AssignmentPair`,
+//! `Stack` or `ElvenDevice`), its standalone entry points, and a `DayN` type
+//! implementing `Solution`. The `solution` registry runs every day generically.
+
+pub mod general;
+pub mod error;
+pub mod answers;
+pub mod bench;
+pub mod generate;
+pub mod params;
+pub mod repl;
+pub mod report;
+pub mod scaffold;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod solution;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod watch;
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+
+pub use solution::{Answer, Part, Solution, Solver, find_solver, registry};
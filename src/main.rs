@@ -1,79 +1,753 @@
-mod general;
-mod day_1;
-mod day_2;
-mod day_3;
-mod day_4;
-mod day_5;
-mod day_6;
-
-use day_1::*;
-use day_2::*;
-use day_3::*;
-use day_4::*;
-use day_5::*;
-use day_6::*;
-
-fn main() {
-    match get_elf_calories() {
-        Result::Ok(ans) => println!("Day 1 Part 1 result: {} Calories\n\n", ans),
-        Result::Err(error) => println!("Error in Day 1 Part 1: {:?}\n\n", error),
-    };
+// Standard library
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
 
-    match get_top_n_elf_calories(3) {
-        Result::Ok(ans) => println!("Day 1 Part 2 result: {} Calories\n\n", ans),
-        Result::Err(error) => println!("Error in Day 1 Part 2: {:?}\n\n", error),
-    };
+// External crates
+use anyhow::{Context, Result, anyhow};
+use clap::{ArgAction, ArgGroup, Parser, Subcommand, ValueEnum};
+use tracing::level_filters::LevelFilter;
 
-    match simulate_input_strategy_results() {
-        Result::Ok(ans) => println!("Day 2 Part 1 result: {} points.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 2 Part 1: {:?}\n\n", error),
-    };
+// Crate modules
+use advent_22::bench::{bench_solver, format_table};
+use advent_22::answers::{ANSWERS_FILE, AnswerStore, Outcome, diff_answers, diff_runs, input_name};
+use advent_22::general::{CONFIG_FILE, InputResolver, ParseCache};
+use advent_22::generate::generate;
+use advent_22::params::{ParamOverride, ParamsConfig};
+use advent_22::report::{Record, solve_all, solve_records, to_csv, to_json};
+use advent_22::scaffold::scaffold_day;
+use advent_22::watch::FileWatcher;
+use advent_22::{Part, Solver, find_solver, registry};
 
-    match simulate_input_strategy_results_2() {
-        Result::Ok(ans) => println!("Day 2 Part 2 result: {} points.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 2 Part 2: {:?}\n\n", error),
-    };
+/// Advent of Code 2022 solutions
+#[derive(Parser)]
+#[command(name = "advent_22", version)]
+struct Cli {
+    /// Log more: -v for progress, -vv for debug traces, -vvv for every trace
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
 
-    match get_total_priority() {
-        Result::Ok(ans) => println!("Day 3 Part 1 result: {} total priority.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 3 Part 1: {:?}\n\n", error),
-    };
+    /// Log less: -q for errors only, -qq for nothing
+    #[arg(short, long, action = ArgAction::Count, global = true, conflicts_with = "verbose")]
+    quiet: u8,
 
-    match get_badges_priority() {
-        Result::Ok(ans) => println!("Day 3 Part 2 result: {} total priority.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 3 Part 2: {:?}\n\n", error),
-    };
+    /// Directory to save parsed inputs in, and load them back from on later runs
+    #[arg(long, global = true)]
+    cache_dir: Option<PathBuf>,
 
-    match get_complete_overlaps() {
-        Result::Ok(ans) => println!("Day 4 Part 1 result: {} complete overlaps.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 4 Part 1: {:?}\n\n", error),
-    };
+    #[command(subcommand)]
+    command: Command,
+}
 
-    match get_partial_overlaps() {
-        Result::Ok(ans) => println!("Day 4 Part 2 result: {} partial overlaps.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 4 Part 2: {:?}\n\n", error),
-    };
+#[derive(Subcommand)]
+enum Command {
+    /// Run the solver for one day (and optionally one part), or for every day
+    #[command(group(ArgGroup::new("selection").required(true).args(["day", "all"])))]
+    Run {
+        /// Day to run
+        #[arg(short, long, value_parser = day_parser)]
+        day: Option<u8>,
+
+        /// Part to run; both parts run if omitted
+        #[arg(short, long, requires = "day", value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Run every day and part
+        #[arg(short, long)]
+        all: bool,
+
+        /// Input file for the selected day, or '-' for stdin
+        #[arg(short, long, requires = "day", conflicts_with = "all")]
+        input: Option<PathBuf>,
+
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// Number of days to run at the same time; also reports each day's wall time
+        #[arg(short, long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        jobs: Option<usize>,
+    },
+
+    /// Run the solvers and compare their answers against the recorded ones
+    Verify {
+        /// Day to verify; every day is verified if omitted
+        #[arg(short, long, value_parser = day_parser)]
+        day: Option<u8>,
+
+        /// Record the answers that are not in the answers file yet
+        #[arg(short, long)]
+        record: bool,
+
+        /// Answers file
+        #[arg(long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+    },
+
+    /// Check the inputs of the selected days, reporting every malformed line
+    Validate {
+        /// Day to validate; every day is validated if omitted
+        #[arg(short, long, value_parser = day_parser)]
+        day: Option<u8>,
+
+        /// Input file for the selected day, or '-' for stdin
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+    },
+
+    /// Re-run a day whenever its input file changes, showing how the answers changed
+    Watch {
+        /// Day to watch
+        #[arg(short, long, value_parser = day_parser)]
+        day: u8,
+
+        /// Part to run; both parts run if omitted
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Input file to watch
+        #[arg(short, long)]
+        input: Option<PathBuf>,
+
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+
+        /// Answers file, whose recorded answers are shown against those of each run
+        #[arg(long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+
+        /// Milliseconds between checks of the input and answers files
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+
+    /// Generate a random valid input for a day, printing the answers it should give
+    Generate {
+        /// Day to generate an input for
+        #[arg(short, long, value_parser = day_parser)]
+        day: u8,
+
+        /// Number of Elves, rounds, Elf groups, pairs, moves or datastream characters
+        #[arg(short, long, default_value_t = 1000)]
+        size: usize,
+
+        /// Seed of the random generator; the same seed gives the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+
+        /// File to write the input to, instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Time parsing and each part of the selected days
+    Bench {
+        /// Day to benchmark; every day is benchmarked if omitted
+        #[arg(short, long, value_parser = day_parser)]
+        day: Option<u8>,
+
+        /// Number of runs of each day
+        #[arg(short, long, default_value_t = 10, value_parser = clap::value_parser!(usize))]
+        runs: usize,
+
+        /// Input file for the selected day
+        #[arg(short, long, requires = "day")]
+        input: Option<PathBuf>,
+
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+    },
+
+    /// List the available days
+    List,
+
+    /// Add the skeleton of a new day: its module, registry entry, example test and README entry
+    New {
+        /// Day to add
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// Title of the day's puzzle
+        #[arg(short, long, default_value = "Untitled")]
+        title: String,
+
+        /// Root of the crate to add the day to
+        #[arg(long, default_value = ".")]
+        root: PathBuf,
+    },
+
+    /// Explore the parsed state of an input interactively (rucksacks, pairs, crane moves, markers)
+    Repl {
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+    },
+
+    /// Answer with the solvers over HTTP: POST an input to /day/{n}/part/{p}
+    #[cfg(feature = "serve")]
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:8022")]
+        address: String,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+    },
+
+    /// Show a terminal dashboard of every day, with their answers, inputs and parsed state
+    #[cfg(feature = "tui")]
+    Tui {
+        /// Directory containing day_N.txt input files
+        #[arg(long)]
+        inputs_dir: Option<PathBuf>,
+
+        /// Day parameter, as KEY=VALUE or day_N.KEY=VALUE, over those of advent.toml (repeatable)
+        #[arg(short = 'P', long = "param", value_name = "KEY=VALUE", value_parser = param_parser)]
+        params: Vec<ParamOverride>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+// A day with a solver in the registry
+fn day_parser(arg: &str) -> Result<u8, String> {
+
+    let day = arg.parse::<u8>()
+    .map_err(|error| format!("'{}' is not a day ({})", arg, error))?;
+
+    let days: Vec<u8> = registry().iter().map(|solver| solver.day()).collect();
+
+    if days.contains(&day) {
+        Ok(day)
+    } else {
+        Err(format!("Day {} is not solved yet (the solved days are {:?})", day, days))
+    }
+}
+
+// Logs go to stderr, so they never mix with the answers. Warnings are shown by default.
+fn init_logging(verbose: u8, quiet: u8) {
 
-    match get_stack_tops() {
-        Result::Ok(ans) => println!("Day 5 Part 1 result: Top stack boxes: {}.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 5 Part 1: {:?}\n\n", error),
+    let level = match (verbose, quiet) {
+        (0, 0) => LevelFilter::WARN,
+        (0, 1) => LevelFilter::ERROR,
+        (0, _) => LevelFilter::OFF,
+        (1, _) => LevelFilter::INFO,
+        (2, _) => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
     };
 
-    match get_stack_tops_with_cratemover_9001() {
-        Result::Ok(ans) => println!("Day 5 Part 2 result: Top stack boxes with CrateMover 9001: {}.\n\n", ans),
-        Result::Err(error) => println!("Error in Day 5 Part 2: {:?}\n\n", error),
+    tracing_subscriber::fmt()
+    .with_max_level(level)
+    .with_writer(io::stderr)
+    .with_ansi(io::stderr().is_terminal())
+    .with_target(false)
+    .init();
+}
+
+fn main() -> ExitCode {
+
+    let cli = Cli::parse();
+
+    init_logging(cli.verbose, cli.quiet);
+
+    ParseCache::global().set_dir(cli.cache_dir);
+
+    let result = match cli.command {
+        Command::Run { day, part, all, input, inputs_dir, params, format, jobs } => {
+            run(day.filter(|_| !all), part, input, inputs_dir, &params, format, jobs)
+        }
+
+        Command::Verify { day, record, answers, inputs_dir } => {
+            verify(day, record, answers, inputs_dir)
+        }
+
+        Command::Validate { day, input, inputs_dir, params } => {
+            validate(day, input, inputs_dir, &params)
+        }
+
+        Command::Watch { day, part, input, inputs_dir, params, answers, interval } => {
+            watch(day, part, input, inputs_dir, &params, answers, Duration::from_millis(interval))
+        }
+
+        Command::Generate { day, size, seed, output } => {
+            generate_input(day, size, seed, output)
+        }
+
+        Command::Bench { day, runs, input, inputs_dir, params } => {
+            bench(day, runs, input, inputs_dir, &params)
+        }
+
+        Command::List => {
+
+            for solver in registry() {
+                println!("Day {}: {}", solver.day(), solver.title());
+            }
+
+            Ok(true)
+        }
+
+        Command::New { day, title, root } => {
+            new_day(day, &title, &root)
+        }
+
+        Command::Repl { params } => {
+            repl(&params)
+        }
+
+        #[cfg(feature = "serve")]
+        Command::Serve { address, params } => {
+            serve(&address, &params)
+        }
+
+        #[cfg(feature = "tui")]
+        Command::Tui { inputs_dir, params } => {
+            tui(inputs_dir, &params)
+        }
     };
 
-    match get_start_of_packet_4_unrepeated() {
-        Result::Ok(Some(ans)) => println!("Day 6 Part 1 result: Start-of-packet position: {}.\n\n", ans),
-        Result::Ok(None) => println!("Day 6 Part 1 result: No start of packet found!"),
-        Result::Err(error) => println!("Error in Day 6 Part 1: {:?}\n\n", error),
+    match result {
+        Result::Ok(true) => ExitCode::SUCCESS,
+        Result::Ok(false) => ExitCode::FAILURE,
+        Result::Err(error) => {
+            eprintln!("Error: {:?}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn param_parser(arg: &str) -> Result<ParamOverride, String> {
+    arg.parse().map_err(|error: anyhow::Error| error.to_string())
+}
+
+// Solvers of the selected day, or of every day, configured for their inputs with the
+// parameters of advent.toml and the command line
+fn select_solvers(day: Option<u8>, resolver: &InputResolver, params: &[ParamOverride]) -> Result<Vec<Box<dyn Solver>>> {
+
+    let mut solvers = match day {
+        Some(day) => vec![find_solver(day)?],
+        None => registry(),
     };
 
-    match get_start_of_packet_14_unrepeated() {
-        Result::Ok(Some(ans)) => println!("Day 6 Part 2 result: Start-of-message position: {}.\n\n", ans),
-        Result::Ok(None) => println!("Day 6 Part 2 result: No start of message found!"),
-        Result::Err(error) => println!("Error in Day 6 Part 2: {:?}\n\n", error),
+    // A parameter for every day must still be a parameter of some day
+    for param in params.iter().filter(|param| param.day().is_none()) {
+        if !solvers.iter().any(|solver| solver.params().contains(&param.key())) {
+            return Err(anyhow!("No selected day has a parameter '{}'", param.key()))
+        }
+    }
+
+    let config = ParamsConfig::load(CONFIG_FILE)?;
+
+    for solver in &mut solvers {
+
+        let day = solver.day();
+        let day_params = config.params(day, &input_name(resolver.resolve(day)), solver.params(), params);
+
+        solver.configure(&day_params)?;
+    }
+
+    Ok(solvers)
+}
+
+fn select_parts(part: Option<u8>) -> Vec<Part> {
+    match part {
+        Some(2) => vec![Part::Two],
+        Some(_) => vec![Part::One],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+// Run the selected solvers, on `jobs` threads if given, and print their answers. Returns
+// whether every solver succeeded.
+fn run(day: Option<u8>, part: Option<u8>, input: Option<PathBuf>, inputs_dir: Option<PathBuf>, params: &[ParamOverride], format: Format, jobs: Option<usize>) -> Result<bool> {
+
+    let resolver = InputResolver::new(input, inputs_dir)?;
+
+    let solvers = select_solvers(day, &resolver, params)?;
+
+    let parts = select_parts(part);
+
+    let start = Instant::now();
+
+    let records = solve_all(&solvers, |day| resolver.input(day), &parts, jobs.unwrap_or(1));
+
+    let wall_time = start.elapsed();
+
+    match format {
+        Format::Text => {
+
+            print_records(&records);
+
+            if let Some(jobs) = jobs {
+                print_wall_times(&records, jobs, wall_time);
+            }
+        }
+        Format::Json => println!("{}", to_json(&records)?),
+        Format::Csv => print!("{}", to_csv(&records)),
+    }
+
+    Ok(records.iter().all(|record| record.succeeded()))
+}
+
+fn print_records(records: &[Record]) {
+
+    for record in records {
+
+        match (&record.answer, &record.failure) {
+            (_, Some(error)) => eprintln!("Error in Day {} Part {}: {:?}", record.day, record.part, error),
+            (Some(answer), None) => println!("Day {} Part {} result: {}", record.day, record.part, answer),
+            (None, None) => (),
+        }
+    }
+}
+
+// Wall time of each day, then of the whole run
+fn print_wall_times(records: &[Record], jobs: usize, wall_time: Duration) {
+
+    println!();
+
+    let mut last_day = None;
+
+    for record in records {
+
+        if last_day != Some(record.day) {
+            println!("Day {} wall time: {:.3} ms", record.day, record.wall_duration_ms);
+            last_day = Some(record.day);
+        }
+    }
+
+    println!("Total wall time: {:.3} ms ({} job(s))", wall_time.as_secs_f64() * 1000.0, jobs);
+}
+
+// Run the selected solvers and compare against the answers file. Returns whether every
+// recorded answer matched.
+fn verify(day: Option<u8>, record: bool, answers: PathBuf, inputs_dir: Option<PathBuf>) -> Result<bool> {
+
+    let resolver = InputResolver::new(None, inputs_dir)?;
+
+    let mut store = AnswerStore::load(answers)?;
+
+    let solvers = select_solvers(day, &resolver, &[])?;
+
+    let mut succeeded = true;
+    let mut recorded = 0;
+
+    for solver in &solvers {
+
+        let day = solver.day();
+        let input_path = resolver.resolve(day);
+        let input_name = input_name(&input_path);
+
+        let results = match solver.run(resolver.input(day), &Part::ALL) {
+            Result::Ok(results) => results,
+            Result::Err(error) => {
+                println!("FAIL  Day {} ({}): {:?}", day, input_name, error);
+                succeeded = false;
+                continue
+            }
+        };
+
+        for (part, result) in Part::ALL.iter().zip(results) {
+
+            match store.check(day, &input_name, *part, result) {
+                Outcome::Match(answer) => {
+                    println!("ok    Day {} Part {} ({}): {}", day, part, input_name, answer);
+                }
+                Outcome::Mismatch { expected, actual } => {
+                    println!("DIFF  Day {} Part {} ({}):", day, part, input_name);
+                    println!("{}", indent(&diff_answers(&expected, &actual.to_string())));
+                    succeeded = false;
+                }
+                Outcome::Unrecorded(answer) if record => {
+                    store.record(day, &input_name, *part, &answer.to_string());
+                    recorded += 1;
+                    println!("new   Day {} Part {} ({}): {} (recorded)", day, part, input_name, answer);
+                }
+                Outcome::Unrecorded(answer) => {
+                    println!("new   Day {} Part {} ({}): {} (not recorded)", day, part, input_name, answer);
+                }
+                Outcome::Failed(error) => {
+                    println!("FAIL  Day {} Part {} ({}): {:?}", day, part, input_name, error);
+                    succeeded = false;
+                }
+            }
+        }
+    }
+
+    if recorded > 0 {
+        store.save()?;
+    }
+
+    Ok(succeeded)
+}
+
+// Check the inputs of the selected solvers, printing every problem found, then a summary.
+// Returns whether every input is valid.
+fn validate(day: Option<u8>, input: Option<PathBuf>, inputs_dir: Option<PathBuf>, params: &[ParamOverride]) -> Result<bool> {
+
+    let resolver = InputResolver::new(input, inputs_dir)?;
+
+    let solvers = select_solvers(day, &resolver, params)?;
+
+    let mut problems = 0;
+
+    for solver in &solvers {
+
+        let day = solver.day();
+        let input = resolver.input(day);
+        let input_name = input.name();
+
+        let errors = solver.validate(input);
+
+        if errors.is_empty() {
+            println!("ok    Day {} ({})", day, input_name);
+            continue
+        }
+
+        println!("FAIL  Day {} ({}): {} problem(s)", day, input_name, errors.len());
+
+        for error in &errors {
+            println!("\n{}", indent(&format!("{:#}", error)));
+        }
+
+        println!();
+
+        problems += errors.len();
+    }
+
+    println!("{} problem(s) in {} input(s)", problems, solvers.len());
+
+    Ok(problems == 0)
+}
+
+// Run a day, then poll its input file and run it again on every change, printing each
+// answer against the one of the previous run. Only returns on error.
+fn watch(day: u8, part: Option<u8>, input: Option<PathBuf>, inputs_dir: Option<PathBuf>, params: &[ParamOverride], answers: PathBuf, interval: Duration) -> Result<bool> {
+
+    if input.as_ref().is_some_and(|input| input.as_os_str() == "-") {
+        return Err(anyhow!("Can't watch stdin, give an input file instead"))
+    }
+
+    let resolver = InputResolver::new(input, inputs_dir)?;
+
+    let solver = select_solvers(Some(day), &resolver, params)?.remove(0);
+
+    let parts = select_parts(part);
+
+    let input_path = resolver.resolve(day);
+    let input_name = input_name(&input_path);
+
+    // Editing the answers file also runs the day again, to compare with the new answers
+    let mut watcher = FileWatcher::new(vec![input_path, answers.clone()]);
+
+    for path in watcher.paths() {
+        println!("Watching {} (Ctrl-C to stop)", path.display());
+    }
+
+    let mut previous = solve_records(solver.as_ref(), resolver.input(day), &parts);
+
+    print_records(&previous);
+    print_recorded(&answers, &input_name, &parts, &previous);
+
+    loop {
+
+        thread::sleep(interval);
+
+        if !watcher.poll() {
+            continue
+        }
+
+        println!("\nInput or answers changed, running Day {} again", day);
+
+        let current = solve_records(solver.as_ref(), resolver.input(day), &parts);
+
+        for (previous_record, record) in previous.iter().zip(&current) {
+
+            match (&previous_record.answer, &record.answer, &record.failure) {
+                (_, _, Some(error)) => eprintln!("Error in Day {} Part {}: {:?}", record.day, record.part, error),
+                (Some(previous_answer), Some(answer), None) if previous_answer == answer => {
+                    println!("Day {} Part {} unchanged: {}", record.day, record.part, answer);
+                }
+                (Some(previous_answer), Some(answer), None) => {
+                    println!("Day {} Part {} changed:", record.day, record.part);
+                    println!("{}", indent(&diff_runs(previous_answer, answer)));
+                }
+                (None, Some(answer), None) => println!("Day {} Part {} result: {}", record.day, record.part, answer),
+                (_, None, None) => (),
+            }
+        }
+
+        print_recorded(&answers, &input_name, &parts, &current);
+
+        previous = current;
+    }
+}
+
+// Show each answer against the one recorded for the input, if any. The answers file is read
+// again every time, since it may have changed too.
+fn print_recorded(answers: &Path, input_name: &str, parts: &[Part], records: &[Record]) {
+
+    let store = match AnswerStore::load(answers) {
+        Result::Ok(store) => store,
+        Result::Err(error) => return eprintln!("Error in the recorded answers: {:?}", error),
     };
 
+    for (part, record) in parts.iter().zip(records) {
+
+        let (Some(answer), Some(expected)) = (&record.answer, store.get(record.day, input_name, *part)) else {
+            continue
+        };
+
+        if answer == expected {
+            println!("Day {} Part {} matches the recorded answer", record.day, record.part);
+        } else {
+            println!("Day {} Part {} differs from the recorded answer:", record.day, record.part);
+            println!("{}", indent(&diff_answers(expected, answer)));
+        }
+    }
+}
+
+// Write a generated input to stdout or a file. The expected answers go to stderr, so they
+// don't end up in the input.
+fn generate_input(day: u8, size: usize, seed: u64, output: Option<PathBuf>) -> Result<bool> {
+
+    let generated = generate(day, size, seed)?;
+
+    match output {
+        Some(output) => {
+            fs::write(&output, &generated.input)
+            .with_context(|| format!("writing generated input to '{}'", output.display()))?;
+        }
+        None => print!("{}", generated.input),
+    }
+
+    eprintln!("Day {} Part 1 expected: {}", day, generated.part_one);
+    eprintln!("Day {} Part 2 expected: {}", day, generated.part_two);
+
+    Ok(true)
+}
+
+fn new_day(day: u8, title: &str, root: &Path) -> Result<bool> {
+
+    for path in scaffold_day(root, day, title)? {
+        println!("Wrote {}", path.display());
+    }
+
+    println!("Fill in tests/fixtures/day_{}_example.txt and the puzzle text, then solve both parts", day);
+
+    Ok(true)
+}
+
+// The prompt is only shown to a person, so piped commands give clean output
+// Inputs are explored with the parameters of the days' usual inputs
+fn repl(params: &[ParamOverride]) -> Result<bool> {
+
+    let solvers = select_solvers(None, &InputResolver::new(None, None)?, params)?;
+
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+
+    advent_22::repl::run(solvers, stdin.lock(), io::stdout(), prompt)?;
+
+    Ok(true)
+}
+
+// Serve until the process is stopped
+#[cfg(feature = "serve")]
+fn serve(address: &str, params: &[ParamOverride]) -> Result<bool> {
+
+    let solvers = select_solvers(None, &InputResolver::new(None, None)?, params)?;
+    let server = advent_22::serve::SolverServer::bind(address, solvers)?;
+
+    if let Some(address) = server.local_addr() {
+        println!("Listening on http://{}", address);
+    }
+
+    server.run();
+
+    Ok(true)
+}
+
+#[cfg(feature = "tui")]
+fn tui(inputs_dir: Option<PathBuf>, params: &[ParamOverride]) -> Result<bool> {
+
+    let resolver = InputResolver::new(None, inputs_dir)?;
+    let solvers = select_solvers(None, &resolver, params)?;
+
+    advent_22::tui::run(solvers, resolver)?;
+
+    Ok(true)
+}
+
+// Benchmark the selected solvers and print a summary table. Returns whether every
+// solver succeeded.
+fn bench(day: Option<u8>, runs: usize, input: Option<PathBuf>, inputs_dir: Option<PathBuf>, params: &[ParamOverride]) -> Result<bool> {
+
+    let resolver = InputResolver::new(input, inputs_dir)?;
+
+    let solvers = select_solvers(day, &resolver, params)?;
+
+    let mut all_timings = Vec::new();
+    let mut succeeded = true;
+
+    for solver in &solvers {
+
+        let day = solver.day();
+
+        match resolver.read(day)
+        .and_then(|input| bench_solver(solver.as_ref(), &input, runs)) {
+            Result::Ok(timings) => all_timings.push(timings),
+            Result::Err(error) => {
+                eprintln!("Error in Day {}: {:?}", day, error);
+                succeeded = false;
+            }
+        }
+    }
+
+    println!("{} run(s) per day\n", runs);
+    print!("{}", format_table(&all_timings));
+
+    Ok(succeeded)
+}
+
+fn indent(text: &str) -> String {
+    text.lines().map(|line| format!("      {}", line)).collect::<Vec<String>>().join("\n")
 }
@@ -0,0 +1,276 @@
+// Standard library
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+// External crates
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Deserialize;
+use toml::{Table, Value};
+
+// Crate modules
+use crate::general::read_text_input;
+
+// Parameters of a day, like the number of top Elves of day 1 or the marker sizes of day 6.
+// Each day reads the ones it knows, with its own defaults.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: Table,
+}
+
+impl Params {
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    pub fn set(&mut self, key: &str, value: impl Into<Value>) {
+        self.values.insert(key.to_string(), value.into());
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|key| key.as_str())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn extend(&mut self, values: &Table) {
+        values.iter().for_each(|(key, value)| self.set(key, value.clone()));
+    }
+
+    // Reject the parameters a day doesn't have, so a typo isn't silently ignored
+    pub fn check_known(&self, day: u8, known: &[&str]) -> Result<()> {
+
+        let unknown: Vec<String> = self.keys()
+        .filter(|key| !known.contains(key))
+        .map(|key| format!("'{}'", key))
+        .collect();
+
+        match (unknown.is_empty(), known.is_empty()) {
+            (true, _) => Ok(()),
+            (false, true) => Err(anyhow!("Day {} has no parameters (got {})", day, unknown.join(", "))),
+            (false, false) => Err(anyhow!(
+                "Day {} has no parameter {} (it has {})",
+                day,
+                unknown.join(", "),
+                known.join(", ")
+            )),
+        }
+    }
+
+    // A positive integer parameter
+    pub fn usize(&self, key: &str, default: usize) -> Result<usize> {
+        match self.values.get(key) {
+            None => Ok(default),
+            Some(Value::Integer(value)) if *value > 0 => Ok(*value as usize),
+            Some(value) => Err(anyhow!("Parameter '{}' must be a positive integer, not {}", key, value)),
+        }
+    }
+
+    pub fn string(&self, key: &str, default: &str) -> Result<String> {
+        match self.values.get(key) {
+            None => Ok(default.to_string()),
+            Some(Value::String(value)) => Ok(value.clone()),
+            Some(value) => Err(anyhow!("Parameter '{}' must be a string, not {}", key, value)),
+        }
+    }
+}
+
+// A parameter given on the command line: `key=value` for every selected day that has the
+// parameter, or `day_N.key=value` for one day. The value is read as TOML if it can be
+// (`10`, `"abc"`), and as a string otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamOverride {
+    day: Option<u8>,
+    key: String,
+    value: Value,
+}
+
+impl ParamOverride {
+
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+}
+
+impl FromStr for ParamOverride {
+    type Err = anyhow::Error;
+
+    fn from_str(arg: &str) -> Result<Self> {
+
+        let Some((name, raw_value)) = arg.split_once('=') else {
+            bail!("Expected KEY=VALUE or day_N.KEY=VALUE, got '{}'", arg)
+        };
+
+        let (day, key) = match name.trim().split_once('.') {
+            Some((day, key)) => {
+
+                let day = day.strip_prefix("day_")
+                .and_then(|day| day.parse::<u8>().ok())
+                .ok_or_else(|| anyhow!("Expected day_N before '.', got '{}'", day))?;
+
+                (Some(day), key)
+            }
+            None => (None, name.trim()),
+        };
+
+        let value = toml::from_str::<Table>(&format!("value = {}", raw_value))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw_value.to_string()));
+
+        Ok(Self { day, key: key.to_string(), value })
+    }
+}
+
+// The [params] table of the configuration file. A day's parameters can be overridden for
+// one input, by the name of its file (without extension):
+//
+// [params.day_6]
+// message_marker = 14
+//
+// [params.day_6.inputs.variant]
+// message_marker = 10
+#[derive(Debug, Default, Deserialize)]
+pub struct ParamsConfig {
+    #[serde(flatten)]
+    days: BTreeMap<String, DayParams>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DayParams {
+    #[serde(default)]
+    inputs: BTreeMap<String, Table>,
+    #[serde(flatten)]
+    values: Table,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    params: ParamsConfig,
+}
+
+impl ParamsConfig {
+
+    // Load the [params] table from a configuration file, if the file exists
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+
+        let path = path.as_ref();
+
+        if !path.try_exists().context("loading configuration file")? {
+            return Ok(Self::default())
+        }
+
+        let config_string = read_text_input(path)
+        .context("loading configuration file")?;
+
+        let config: ConfigFile = toml::from_str(&config_string)
+        .with_context(|| format!("parsing configuration file '{}'", path.display()))?;
+
+        Ok(config.params)
+    }
+
+    // Parameters of a day on an input. In order of precedence: the command line, the
+    // input's table, then the day's table. Command-line parameters for every day only
+    // apply to the days that have them.
+    pub fn params(&self, day: u8, input_name: &str, known: &[&str], overrides: &[ParamOverride]) -> Params {
+
+        let mut params = Params::new();
+
+        if let Some(day_params) = self.days.get(&format!("day_{}", day)) {
+
+            params.extend(&day_params.values);
+
+            if let Some(input_params) = day_params.inputs.get(input_name) {
+                params.extend(input_params);
+            }
+        }
+
+        for param in overrides {
+            match param.day {
+                Some(param_day) if param_day != day => (),
+                None if !known.contains(&param.key.as_str()) => (),
+                _ => params.set(&param.key, param.value.clone()),
+            }
+        }
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+[inputs]
+dir = \"inputs\"
+
+[params.day_6]
+packet_marker = 5
+message_marker = 12
+
+[params.day_6.inputs.variant]
+message_marker = 10
+";
+
+    #[test]
+    fn command_line_then_input_then_day() {
+
+        let config = toml::from_str::<ConfigFile>(CONFIG).unwrap().params;
+        let known = ["packet_marker", "message_marker"];
+
+        let params = config.params(6, "day_6", &known, &[]);
+        assert_eq!((params.usize("packet_marker", 4).unwrap(), params.usize("message_marker", 14).unwrap()), (5, 12));
+
+        let params = config.params(6, "variant", &known, &[]);
+        assert_eq!(params.usize("message_marker", 14).unwrap(), 10);
+
+        let overrides = ["message_marker=8".parse().unwrap(), "day_1.top=2".parse().unwrap()];
+
+        let params = config.params(6, "variant", &known, &overrides);
+        assert_eq!(params, Params::new().with("packet_marker", 5).with("message_marker", 8));
+
+        assert!(config.params(4, "day_4", &[], &overrides).is_empty());
+    }
+
+    #[test]
+    fn override_values() {
+
+        let param: ParamOverride = "day_3.priorities=abc".parse().unwrap();
+        assert_eq!((param.day(), param.key()), (Some(3), "priorities"));
+        assert_eq!(param.value, Value::String("abc".to_string()));
+
+        let param: ParamOverride = "top=5".parse().unwrap();
+        assert_eq!((param.day(), param.value), (None, Value::Integer(5)));
+
+        assert!("top".parse::<ParamOverride>().is_err());
+        assert!("day_x.top=5".parse::<ParamOverride>().is_err());
+    }
+
+    #[test]
+    fn typed_values_and_unknown_keys() {
+
+        let params = Params::new().with("top", 0).with("name", "x");
+
+        assert!(params.usize("top", 3).is_err());
+        assert!(params.string("top", "").is_err());
+        assert_eq!(params.string("name", "").unwrap(), "x");
+        assert_eq!(params.usize("other", 3).unwrap(), 3);
+
+        assert!(params.check_known(1, &["top", "name"]).is_ok());
+        assert!(params.check_known(1, &["top"]).is_err());
+    }
+}
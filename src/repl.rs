@@ -0,0 +1,415 @@
+// Standard library
+use std::io::{BufRead, Write};
+
+// External crates
+use anyhow::{Context, Result, anyhow, bail};
+
+// Crate modules
+use crate::day_3::{Day3, ItemTypePriorities, Rucksack};
+use crate::day_4::AssignmentPair;
+use crate::day_5::{Movement, Stack};
+use crate::day_6::{Day6, ElvenDevice};
+use crate::general::{Input, InputResolver};
+use crate::solution::{Solver, registry};
+
+const HELP: &str = "\
+Commands:
+  load dayN [FILE]    load an input of day 3, 4, 5 or 6 (by default, the day's usual input)
+  help                show this help
+  quit                leave (or Ctrl-D)
+
+Day 3:
+  rucksack N          compartments and shared item of the Nth rucksack
+  priority ITEM       priority of an item type
+
+Day 4:
+  pair N              ranges and overlaps of the Nth assignment pair
+
+Day 5:
+  show stacks         draw the stacks
+  top                 crates on top of each stack
+  step [N]            apply the next move, or the next N moves
+  crane 9000|9001     crane used by the next moves
+  reset               go back to the drawing, before any move
+
+Day 6:
+  marker [N]          first marker of N distinct characters, or the configured markers
+  window END N        the N characters ending at character END, and whether they are distinct";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Crane {
+    CrateMover9000,
+    CrateMover9001,
+}
+
+// What the loaded input was parsed into, with the day's parameters it's explored with
+enum State {
+    Empty,
+    Rucksacks {
+        rucksacks: Vec<Rucksack>,
+        priorities: ItemTypePriorities,
+    },
+    Assignments(Vec<AssignmentPair>),
+    Crates {
+        initial: Stack,
+        stack: Stack,
+        moves: Vec<Movement>,
+        next: usize,
+        crane: Crane,
+    },
+    Datastream {
+        device: ElvenDevice,
+        markers: [usize; 2],
+    },
+}
+
+// Explores the parsed state of one input at a time, one command per line. Inputs are
+// parsed by the given solvers, so they are explored with the solvers' parameters.
+pub struct Repl {
+    solvers: Vec<Box<dyn Solver>>,
+    state: State,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new(registry())
+    }
+}
+
+impl Repl {
+
+    pub fn new(solvers: Vec<Box<dyn Solver>>) -> Self {
+        Self { solvers, state: State::Empty }
+    }
+
+    // Run one command, returning what to print
+    pub fn execute(&mut self, command_line: &str) -> Result<String> {
+
+        let words: Vec<&str> = command_line.split_whitespace().collect();
+
+        match words.as_slice() {
+            [] => Ok(String::new()),
+            ["help"] => Ok(HELP.to_string()),
+            ["load", day] => self.load(day, None),
+            ["load", day, ..] => {
+                // The path is the rest of the line, which may have spaces
+                let path = command_line.trim_start()["load".len()..].trim_start()[day.len()..].trim();
+                self.load(day, Some(path))
+            }
+            [command, args @ ..] => self.query(command, args),
+        }
+    }
+
+    fn load(&mut self, day: &str, path: Option<&str>) -> Result<String> {
+
+        let day_number = day.trim_start_matches("day").trim_start_matches('_').parse::<u8>()
+        .map_err(|_| anyhow!("Expected a day like 'day5', got '{}'", day))?;
+
+        let input = match path {
+            // The commands are read from stdin, so it can't be an input too
+            Some("-") => bail!("Can't load an input from stdin, give an input file instead"),
+            Some(path) => Input::from_arg(path),
+            None => InputResolver::new(None, None)?.input(day_number),
+        };
+
+        let input_name = input.name();
+
+        let Some(solver) = self.solvers.iter().find(|solver| solver.day() == day_number) else {
+            bail!("Day {} is not solved yet", day_number)
+        };
+
+        let (state, summary) = match day_number {
+            3 => {
+                let priorities = ItemTypePriorities::from_alphabet(solver.downcast_ref::<Day3>()?.priorities())?;
+                let rucksacks = solver.parse_as::<Vec<Rucksack>>(input)?;
+                let summary = format!("{} rucksacks", rucksacks.len());
+                (State::Rucksacks { rucksacks, priorities }, summary)
+            }
+            4 => {
+                let assignments_vec = solver.parse_as::<Vec<AssignmentPair>>(input)?;
+                let summary = format!("{} assignment pairs", assignments_vec.len());
+                (State::Assignments(assignments_vec), summary)
+            }
+            5 => {
+                let (stack, moves) = solver.parse_as::<(Stack, Vec<Movement>)>(input)?;
+                let summary = format!("{} stacks, {} moves", stack.num_stacks(), moves.len());
+                (State::Crates { initial: stack.clone(), stack, moves, next: 0, crane: Crane::CrateMover9000 }, summary)
+            }
+            6 => {
+                let day_6 = solver.downcast_ref::<Day6>()?;
+                let markers = [day_6.packet_marker(), day_6.message_marker()];
+                let device = solver.parse_as::<ElvenDevice>(input)?;
+                let summary = format!("{} characters", device.datastream().len());
+                (State::Datastream { device, markers }, summary)
+            }
+            _ => bail!("Day {} can't be explored here, only days 3 to 6", day_number),
+        };
+
+        self.state = state;
+
+        Ok(format!("Loaded Day {} from '{}': {}", day_number, input_name, summary))
+    }
+
+    fn query(&mut self, command: &str, args: &[&str]) -> Result<String> {
+
+        match (&mut self.state, command, args) {
+
+            (State::Rucksacks { rucksacks, priorities }, "rucksack", [n]) => {
+
+                let rucksack = nth(rucksacks, number(n)?, "rucksack")?;
+                let (compartment_1, compartment_2) = rucksack.compartments();
+                let priority = priorities.get_item_priority(rucksack.wrong_item())?;
+
+                Ok(format!(
+                    "Rucksack {}: {} | {}\nShared item: {} (priority {})",
+                    n, compartment_1, compartment_2, rucksack.wrong_item(), priority
+                ))
+            }
+
+            (State::Rucksacks { priorities, .. }, "priority", [item]) => {
+
+                let mut chars = item.chars();
+
+                let (Some(item_type), None) = (chars.next(), chars.next()) else {
+                    bail!("Expected a single item type, got '{}'", item)
+                };
+
+                Ok(format!("{}", priorities.get_item_priority(item_type)?))
+            }
+
+            (State::Assignments(assignments_vec), "pair", [n]) => {
+
+                let pair = nth(assignments_vec, number(n)?, "assignment pair")?;
+                let (elf_1, elf_2) = (pair.elf_1(), pair.elf_2());
+
+                Ok(format!(
+                    "Pair {}: {}-{} and {}-{}\nFully contained: {}\nOverlapping: {}",
+                    n, elf_1.0, elf_1.1, elf_2.0, elf_2.1, yes_no(pair.total_overlap()), yes_no(pair.simple_overlap())
+                ))
+            }
+
+            (State::Crates { stack, .. }, "show", [] | ["stacks"]) => Ok(stack.to_string()),
+
+            (State::Crates { stack, .. }, "top", []) => Ok(stack.top_boxes()),
+
+            (State::Crates { stack, moves, next, crane, .. }, "step", args) => {
+
+                let steps = match args {
+                    [] => 1,
+                    [n] => number(n)?,
+                    _ => bail!("Usage: step [N]"),
+                };
+
+                let mut output: Vec<String> = Vec::new();
+
+                for _ in 0..steps {
+
+                    let Some(movement) = moves.get(*next) else {
+                        output.push("No moves left".to_string());
+                        break
+                    };
+
+                    let movement = std::slice::from_ref(movement);
+
+                    let tops = match crane {
+                        Crane::CrateMover9000 => stack.move_boxes(movement)?,
+                        Crane::CrateMover9001 => stack.move_boxes_with_cratemover_9001(movement)?,
+                    };
+
+                    *next += 1;
+
+                    output.push(format!("Move {}/{}: {}, tops {}", next, moves.len(), movement[0], tops));
+                }
+
+                Ok(output.join("\n"))
+            }
+
+            (State::Crates { crane, .. }, "crane", [model]) => {
+
+                *crane = match *model {
+                    "9000" => Crane::CrateMover9000,
+                    "9001" => Crane::CrateMover9001,
+                    _ => bail!("Expected crane 9000 or 9001, got '{}'", model),
+                };
+
+                Ok(format!("Using the CrateMover {}", model))
+            }
+
+            (State::Crates { initial, stack, next, .. }, "reset", []) => {
+
+                *stack = initial.clone();
+                *next = 0;
+
+                Ok(stack.to_string())
+            }
+
+            (State::Datastream { device, .. }, "marker", [size]) => Ok(marker(device, number(size)?)),
+
+            (State::Datastream { device, markers }, "marker", []) => {
+                Ok(markers.iter().map(|size| marker(device, *size)).collect::<Vec<String>>().join("\n"))
+            }
+
+            (State::Datastream { device, .. }, "window", [end, size]) => {
+
+                let (end, size) = (number(end)?, number(size)?);
+
+                if size > end || end > device.datastream().len() {
+                    bail!("The datastream has {} characters", device.datastream().len())
+                }
+
+                let window = &device.datastream()[end - size..end];
+                let distinct = ElvenDevice::unrepeated_start_of_packet(window);
+
+                Ok(format!(
+                    "Characters {} to {}: {}, {}",
+                    end - size + 1, end, window.iter().collect::<String>(), if distinct { "all different" } else { "some repeat" }
+                ))
+            }
+
+            (State::Empty, _, _) => bail!("Unknown command '{}' with no input loaded (try 'help')", command),
+
+            _ => bail!("Unknown command '{}' for the loaded day (try 'help')", command),
+        }
+    }
+}
+
+fn marker(device: &ElvenDevice, size: usize) -> String {
+    match device.get_start_of_packet(size, &ElvenDevice::unrepeated_start_of_packet) {
+        Some(end) => format!(
+            "First marker of {} characters ends at character {} ({})",
+            size, end, device.datastream()[end - size..end].iter().collect::<String>()
+        ),
+        None => format!("No marker of {} distinct characters", size),
+    }
+}
+
+// Read commands until 'quit' or the end of the input, printing a prompt before each one if
+// asked. Errors are printed, and don't stop the loop.
+pub fn run(solvers: Vec<Box<dyn Solver>>, input: impl BufRead, mut output: impl Write, prompt: bool) -> Result<()> {
+
+    let mut repl = Repl::new(solvers);
+    let mut lines = input.lines();
+
+    loop {
+
+        if prompt {
+            write!(output, "advent> ")?;
+            output.flush()?;
+        }
+
+        let Some(command_line) = lines.next() else {
+            break
+        };
+
+        let command_line = command_line.context("reading command")?;
+
+        if matches!(command_line.trim(), "quit" | "exit") {
+            break
+        }
+
+        match repl.execute(&command_line) {
+            Result::Ok(text) if text.is_empty() => (),
+            Result::Ok(text) => writeln!(output, "{}", text)?,
+            Result::Err(error) => writeln!(output, "error: {:#}", error)?,
+        }
+    }
+
+    Ok(())
+}
+
+// A 1-based index or a count, as typed
+fn number(arg: &str) -> Result<usize> {
+    match arg.parse::<usize>() {
+        Result::Ok(number) if number > 0 => Ok(number),
+        _ => Err(anyhow!("Expected a positive number, got '{}'", arg)),
+    }
+}
+
+fn nth<'a, T>(items: &'a [T], n: usize, what: &str) -> Result<&'a T> {
+    items.get(n - 1).ok_or_else(|| anyhow!("There is no {} {} (there are {})", what, n, items.len()))
+}
+
+fn yes_no(answer: bool) -> &'static str {
+    if answer { "yes" } else { "no" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+
+    fn fixture(day: u8) -> String {
+        format!("{}/tests/fixtures/day_{}_example.txt", env!("CARGO_MANIFEST_DIR"), day)
+    }
+
+    #[test]
+    fn crane_steps() {
+
+        let mut repl = Repl::default();
+
+        assert!(repl.execute("top").is_err());
+        assert!(repl.execute(&format!("load day5 {}", fixture(5))).unwrap().ends_with("3 stacks, 4 moves"));
+
+        assert_eq!(repl.execute("step").unwrap(), "Move 1/4: move 1 from 2 to 1, tops DCP");
+        assert_eq!(repl.execute("show stacks").unwrap(), "[D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3");
+        assert_eq!(repl.execute("step 5").unwrap().lines().last(), Some("No moves left"));
+        assert_eq!(repl.execute("top").unwrap(), "CMZ");
+
+        repl.execute("reset").unwrap();
+        repl.execute("crane 9001").unwrap();
+        repl.execute("step 4").unwrap();
+        assert_eq!(repl.execute("top").unwrap(), "MCD");
+
+        assert!(repl.execute("marker 4").is_err());
+    }
+
+    #[test]
+    fn rucksacks_pairs_and_markers() {
+
+        let mut repl = Repl::default();
+
+        repl.execute(&format!("load day_3 {}", fixture(3))).unwrap();
+        assert_eq!(repl.execute("rucksack 2").unwrap(), "Rucksack 2: jqHRNqRjqzjGDLGL | rsFMfFZSrLrFZsSL\nShared item: L (priority 38)");
+        assert!(repl.execute("rucksack 7").is_err());
+        assert!(repl.execute("load day5 -").is_err());
+
+        repl.execute(&format!("load 4 {}", fixture(4))).unwrap();
+        assert_eq!(repl.execute("pair 4").unwrap(), "Pair 4: 2-8 and 3-7\nFully contained: yes\nOverlapping: yes");
+
+        repl.execute(&format!("load day6 {}", fixture(6))).unwrap();
+        assert_eq!(repl.execute("marker 4").unwrap(), "First marker of 4 characters ends at character 7 (jpqm)");
+        assert_eq!(repl.execute("window 4 4").unwrap(), "Characters 1 to 4: mjqj, some repeat");
+    }
+
+    #[test]
+    fn configured_days() {
+
+        let mut solvers = registry();
+
+        solvers[2].configure(&Params::new().with("priorities", "ZYXWVUTSRQPONMLKJIHGFEDCBAzyxwvutsrqponmlkjihgfedcba")).unwrap();
+        solvers[5].configure(&Params::new().with("packet_marker", 5)).unwrap();
+
+        let mut repl = Repl::new(solvers);
+
+        repl.execute(&format!("load day3 {}", fixture(3))).unwrap();
+        assert_eq!(repl.execute("rucksack 2").unwrap(), "Rucksack 2: jqHRNqRjqzjGDLGL | rsFMfFZSrLrFZsSL\nShared item: L (priority 15)");
+        assert_eq!(repl.execute("priority a").unwrap(), "52");
+
+        repl.execute(&format!("load day6 {}", fixture(6))).unwrap();
+        assert_eq!(repl.execute("marker").unwrap(), "First marker of 5 characters ends at character 8 (jpqmg)\nFirst marker of 14 characters ends at character 19 (qmgbljsphdztnv)");
+    }
+
+    #[test]
+    fn loop_reports_errors_and_stops_at_quit() {
+
+        let commands = "help\nbogus\n\nquit\nhelp\n";
+        let mut output: Vec<u8> = Vec::new();
+
+        run(registry(), commands.as_bytes(), &mut output, false).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("Commands:"));
+        assert!(output.ends_with("error: Unknown command 'bogus' with no input loaded (try 'help')\n"));
+    }
+}
@@ -0,0 +1,252 @@
+// Standard library
+use std::any::Any;
+use std::panic;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// External crates
+use anyhow::Result;
+use serde::Serialize;
+
+// Crate modules
+use crate::general::{Input, ParseCache};
+use crate::solution::{Part, Solver};
+
+// The outcome of one day and part
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub answer_type: Option<String>,
+    pub parse_duration_ms: f64,
+    pub duration_ms: f64,
+    // Reading, parsing and solving every requested part of the day
+    pub wall_duration_ms: f64,
+    pub input: String,
+    // Error messages, outermost context first
+    pub error: Option<Vec<String>>,
+    #[serde(skip)]
+    pub failure: Option<Arc<anyhow::Error>>,
+}
+
+impl Record {
+
+    fn new(day: u8, part: Part, input_name: &str) -> Self {
+        Self {
+            day,
+            part: part.number(),
+            answer: None,
+            answer_type: None,
+            parse_duration_ms: 0.0,
+            duration_ms: 0.0,
+            wall_duration_ms: 0.0,
+            input: input_name.to_string(),
+            error: None,
+            failure: None,
+        }
+    }
+
+    fn fail(&mut self, error: Arc<anyhow::Error>) {
+        self.error = Some(error.chain().map(|cause| cause.to_string()).collect());
+        self.failure = Some(error);
+    }
+
+    pub fn succeeded(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+fn as_millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+// Solve the requested parts of a day, timing parsing and each part. The input is parsed
+// through the process's cache, so its parse time is near zero if it was already parsed. A
+// failure to read or parse the input fails every requested part.
+pub fn solve_records(solver: &dyn Solver, input: Input, parts: &[Part]) -> Vec<Record> {
+    solve_records_with(solver, input, parts, Some(ParseCache::global()))
+}
+
+// Same as `solve_records`, through the given cache or none, for inputs that are only
+// solved once
+pub fn solve_records_with(solver: &dyn Solver, input: Input, parts: &[Part], cache: Option<&ParseCache>) -> Vec<Record> {
+
+    let day = solver.day();
+    let input_name = input.name();
+
+    let mut records: Vec<Record> = parts
+    .iter()
+    .map(|part| Record::new(day, *part, &input_name))
+    .collect();
+
+    let wall_start = Instant::now();
+
+    let start = Instant::now();
+    let parsed: Result<Arc<dyn Any>> = match cache {
+        Some(cache) => solver.parse_cached(input, cache).map(|parsed| parsed as Arc<dyn Any>),
+        None => solver.parse(input).map(Arc::from),
+    };
+    let parse_duration_ms = as_millis(start.elapsed());
+
+    let parsed = match parsed {
+        Result::Ok(parsed) => parsed,
+        Result::Err(error) => {
+
+            let error = Arc::new(error);
+
+            for record in records.iter_mut() {
+                record.parse_duration_ms = parse_duration_ms;
+                record.wall_duration_ms = as_millis(wall_start.elapsed());
+                record.fail(error.clone());
+            }
+
+            return records
+        }
+    };
+
+    for (record, part) in records.iter_mut().zip(parts) {
+
+        record.parse_duration_ms = parse_duration_ms;
+
+        let start = Instant::now();
+        let answer = solver.solve_part(parsed.as_ref(), *part);
+        record.duration_ms = as_millis(start.elapsed());
+
+        match answer {
+            Result::Ok(answer) => {
+                record.answer = Some(answer.to_string());
+                record.answer_type = Some(answer.kind().to_string());
+            }
+            Result::Err(error) => record.fail(Arc::new(error)),
+        }
+    }
+
+    let wall_duration_ms = as_millis(wall_start.elapsed());
+
+    for record in records.iter_mut() {
+        record.wall_duration_ms = wall_duration_ms;
+    }
+
+    records
+}
+
+// Solve the requested parts of several days on up to `jobs` threads, each taking the next
+// day not started yet. The records come back in the order of the solvers, whichever
+// finishes first.
+pub fn solve_all<F>(solvers: &[Box<dyn Solver>], inputs: F, parts: &[Part], jobs: usize) -> Vec<Record>
+where
+    F: Fn(u8) -> Input + Sync
+{
+
+    let jobs = jobs.clamp(1, solvers.len().max(1));
+    let next_solver = AtomicUsize::new(0);
+
+    let mut solved: Vec<(usize, Vec<Record>)> = thread::scope(|scope| {
+
+        let workers: Vec<_> = (0..jobs)
+        .map(|_| scope.spawn(|| {
+
+            let mut solved = Vec::new();
+
+            loop {
+
+                let index = next_solver.fetch_add(1, Ordering::Relaxed);
+
+                let Some(solver) = solvers.get(index) else {
+                    break
+                };
+
+                solved.push((index, solve_records(solver.as_ref(), inputs(solver.day()), parts)));
+            }
+
+            solved
+        }))
+        .collect();
+
+        workers
+        .into_iter()
+        .flat_map(|worker| worker.join().unwrap_or_else(|panic| panic::resume_unwind(panic)))
+        .collect()
+    });
+
+    solved.sort_by_key(|(index, _)| *index);
+
+    solved.into_iter().flat_map(|(_, records)| records).collect()
+}
+
+pub fn to_json(records: &[Record]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(records)?)
+}
+
+pub fn to_csv(records: &[Record]) -> String {
+
+    let mut csv = String::from("day,part,answer,answer_type,parse_duration_ms,duration_ms,wall_duration_ms,input,error\n");
+
+    for record in records {
+
+        let fields = [
+            record.day.to_string(),
+            record.part.to_string(),
+            record.answer.clone().unwrap_or_default(),
+            record.answer_type.clone().unwrap_or_default(),
+            format!("{:.3}", record.parse_duration_ms),
+            format!("{:.3}", record.duration_ms),
+            format!("{:.3}", record.wall_duration_ms),
+            record.input.clone(),
+            record.error.as_ref().map(|chain| chain.join(": ")).unwrap_or_default(),
+        ];
+
+        let escaped: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+
+        csv.push_str(&escaped.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// Quote a CSV field if it contains a separator, a quote or a line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::registry;
+
+    #[test]
+    fn parallel_records_keep_solver_order() {
+
+        let examples = [
+            include_str!("../tests/fixtures/day_1_example.txt"),
+            include_str!("../tests/fixtures/day_2_example.txt"),
+            include_str!("../tests/fixtures/day_3_example.txt"),
+            include_str!("../tests/fixtures/day_4_example.txt"),
+            include_str!("../tests/fixtures/day_5_example.txt"),
+            include_str!("../tests/fixtures/day_6_example.txt"),
+        ];
+
+        let records = solve_all(&registry()[..examples.len()], |day| Input::from(examples[day as usize - 1]), &Part::ALL, 4);
+
+        let days_and_parts: Vec<(u8, u8)> = records.iter().map(|record| (record.day, record.part)).collect();
+
+        assert_eq!(days_and_parts, (1..=6).flat_map(|day| [(day, 1), (day, 2)]).collect::<Vec<_>>());
+        assert!(records.iter().all(|record| record.succeeded()));
+        assert_eq!(records[8].answer.as_deref(), Some("CMZ"));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted_when_needed() {
+        assert_eq!(csv_field("CMZ"), "CMZ");
+        assert_eq!(csv_field("a, b"), "\"a, b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
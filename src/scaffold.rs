@@ -0,0 +1,190 @@
+// Standard library
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// External crates
+use anyhow::{Context, Result, anyhow, bail};
+
+// Skeleton of a day module, in the shape of the solved days
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.in");
+
+// A file to write, with its new contents
+struct Edit {
+    path: PathBuf,
+    contents: String,
+}
+
+// Add a new day to the crate at `root`: its module skeleton, its registry entry, an
+// example test (ignored) with an empty fixture, and its README index entry. Every file is
+// checked before any is written. Returns the files written.
+pub fn scaffold_day(root: &Path, day: u8, title: &str) -> Result<Vec<PathBuf>> {
+
+    let module = root.join(format!("src/day_{}.rs", day));
+
+    if module.exists() {
+        bail!("Day {} already exists in '{}'", day, module.display());
+    }
+
+    // Days are added in order, so the registry and the index stay numbered by day
+    if day > 1 && !root.join(format!("src/day_{}.rs", day - 1)).exists() {
+        bail!("Day {} comes first, days are added in order", day - 1);
+    }
+
+    let fixture = root.join(format!("tests/fixtures/day_{}_example.txt", day));
+
+    let module_contents = DAY_TEMPLATE
+    .replace("{{day}}", &day.to_string())
+    .replace("\"{{title}}\"", &format!("{:?}", title))
+    .replace("{{title}}", &block_comment_text(title));
+
+    let mut edits = vec![
+        Edit { path: module, contents: module_contents },
+        edit_file(root, "src/lib.rs", |text| {
+            insert_after_last(text, "pub mod day_", &format!("pub mod day_{};", day))
+        })?,
+        edit_file(root, "src/solution.rs", |text| {
+            let text = insert_after_last(text, "use crate::day_", &format!("use crate::day_{0}::Day{0};", day))?;
+            insert_after_last(&text, "Box::new(Day", &format!("Box::new(Day{}),", day))
+        })?,
+        edit_file(root, "tests/examples.rs", |text| {
+
+            let constant = format!(
+                "const DAY_{0}_EXAMPLE: &str = include_str!(\"fixtures/day_{0}_example.txt\");",
+                day
+            );
+
+            let text = insert_after_last(text, "const DAY_", &constant)?;
+
+            Ok(text + &example_test(day))
+        })?,
+        edit_file(root, "README.md", |text| {
+            insert_after_last(text, "[**Day ", &format!("{0}. [**Day {0}**](src/day_{0}.rs)", day))
+        })?,
+    ];
+
+    // Keep an example that was saved before the day
+    if !fixture.exists() {
+        edits.push(Edit { path: fixture, contents: String::new() });
+    }
+
+    for edit in &edits {
+        fs::write(&edit.path, &edit.contents)
+        .with_context(|| format!("writing '{}'", edit.path.display()))?;
+    }
+
+    Ok(edits.into_iter().map(|edit| edit.path).collect())
+}
+
+fn edit_file(root: &Path, file: &str, edit: impl FnOnce(&str) -> Result<String>) -> Result<Edit> {
+
+    let path = root.join(file);
+
+    let text = fs::read_to_string(&path)
+    .with_context(|| format!("reading '{}' (is '{}' the crate root?)", path.display(), root.display()))?;
+
+    let contents = edit(&text)
+    .with_context(|| format!("editing '{}'", path.display()))?;
+
+    Ok(Edit { path, contents })
+}
+
+// Insert a line after the last line containing `pattern`, with the same indentation, so
+// new entries follow the existing ones
+fn insert_after_last(text: &str, pattern: &str, new_line: &str) -> Result<String> {
+
+    let mut lines: Vec<&str> = text.lines().collect();
+
+    let Some(index) = lines.iter().rposition(|line| line.contains(pattern)) else {
+        return Err(anyhow!("No line contains '{}'", pattern))
+    };
+
+    let indentation = &lines[index][..lines[index].len() - lines[index].trim_start().len()];
+    let new_line = format!("{}{}", indentation, new_line);
+
+    lines.insert(index + 1, &new_line);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// Ignored until the day is solved and the expected answers are filled in
+fn example_test(day: u8) -> String {
+    format!(
+        "
+#[test]
+#[ignore = \"Day {0} is not solved yet\"]
+fn day_{0}_example() -> Result<()> {{
+
+    assert_eq!(solve_both({0}, DAY_{0}_EXAMPLE)?, (\"\".to_string(), \"\".to_string()));
+
+    Ok(())
+}}
+",
+        day
+    )
+}
+
+// Text that can't end the block comment it's put in, nor open a nested one
+fn block_comment_text(text: &str) -> String {
+    text.replace("*/", "* /").replace("/*", "/ *")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn new_lines_follow_the_last_match() {
+
+        let text = "mod a;\n    Box::new(Day1),\n    Box::new(Day2),\n];\n";
+
+        assert_eq!(
+            insert_after_last(text, "Box::new(Day", "Box::new(Day3),").unwrap(),
+            "mod a;\n    Box::new(Day1),\n    Box::new(Day2),\n    Box::new(Day3),\n];\n"
+        );
+
+        assert!(insert_after_last(text, "pub mod", "pub mod b;").is_err());
+    }
+
+    #[test]
+    fn titles_stay_inside_the_comment() {
+        assert_eq!(block_comment_text("Ends */ here"), "Ends * / here");
+        assert_eq!(block_comment_text("/*/"), "/ * /");
+        assert_eq!(block_comment_text("No Space Left On Device"), "No Space Left On Device");
+    }
+
+    #[test]
+    fn scaffold_a_copy_of_the_crate() {
+
+        let source = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = TempDir::new("scaffold");
+
+        for file in ["src/lib.rs", "src/solution.rs", "src/day_6.rs", "tests/examples.rs", "README.md"] {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::copy(source.join(file), root.join(file)).unwrap();
+        }
+
+        fs::create_dir_all(root.join("tests/fixtures")).unwrap();
+
+        // Day 8 can't come before day 7
+        assert!(scaffold_day(&root, 8, "Treetop Tree House").is_err());
+
+        let written = scaffold_day(&root, 7, "No Space \"Left\"").unwrap();
+        assert_eq!(written.len(), 6);
+
+        let module = fs::read_to_string(root.join("src/day_7.rs")).unwrap();
+        assert!(module.contains("/*--- Day 7: No Space \"Left\" ---"));
+        assert!(module.contains("const TITLE: &'static str = \"No Space \\\"Left\\\"\";"));
+        assert!(module.contains("impl Solution for Day7 {"));
+
+        let solution = fs::read_to_string(root.join("src/solution.rs")).unwrap();
+        assert!(solution.contains("use crate::day_6::Day6;\nuse crate::day_7::Day7;\n"));
+        assert!(solution.contains("        Box::new(Day6::default()),\n        Box::new(Day7),\n"));
+
+        let readme = fs::read_to_string(root.join("README.md")).unwrap();
+        assert!(readme.contains("6. [**Day 6**](src/day_6.rs)\n7. [**Day 7**](src/day_7.rs)"));
+
+        // A day is only scaffolded once
+        assert!(scaffold_day(&root, 7, "").is_err());
+    }
+}
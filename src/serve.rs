@@ -0,0 +1,170 @@
+// Standard library
+use std::net::SocketAddr;
+
+// External crates
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
+use tiny_http::{Header, Method, Request, Response, Server};
+use tracing::info;
+
+// Crate modules
+use crate::general::Input;
+use crate::report::solve_records_with;
+use crate::solution::{Part, Solver};
+
+// A local HTTP server answering with the solvers, for tools written in other languages.
+// Every response is JSON:
+//
+// GET  /days               the days, with their titles and parameters
+// POST /day/{n}            both parts of day n, on the request body as input
+// POST /day/{n}/part/{p}   part p of day n, on the request body as input
+//
+// Answers are the records of `run --format json`, with a 422 status if solving failed.
+// Other errors are `{"error": [...]}`, outermost context first.
+pub struct SolverServer {
+    server: Server,
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl SolverServer {
+
+    pub fn bind(address: &str, solvers: Vec<Box<dyn Solver>>) -> Result<Self> {
+
+        let server = Server::http(address)
+        .map_err(|error| anyhow!(error))
+        .with_context(|| format!("listening on {}", address))?;
+
+        Ok(Self { server, solvers })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.server.server_addr().to_ip()
+    }
+
+    // Answer requests one at a time, until `stop` is called
+    pub fn run(&self) {
+        for request in self.server.incoming_requests() {
+            self.answer(request);
+        }
+    }
+
+    pub fn stop(&self) {
+        self.server.unblock();
+    }
+
+    fn answer(&self, mut request: Request) {
+
+        let method = request.method().clone();
+        let url = request.url().to_string();
+
+        let mut body = String::new();
+
+        let (status, json) = match request.as_reader().read_to_string(&mut body) {
+            Result::Ok(_) => self.respond(&method, &url, body),
+            Result::Err(error) => (400, error_json(&anyhow!(error).context("reading request body"))),
+        };
+
+        info!("{} {} {}", method, url, status);
+
+        let header = Header::from_bytes("Content-Type", "application/json")
+        .expect("a valid header");
+
+        let response = Response::from_string(json.to_string())
+        .with_status_code(status)
+        .with_header(header);
+
+        // The client may be gone, and there is nobody else to tell
+        let _ = request.respond(response);
+    }
+
+    // The status and JSON body for a request
+    pub fn respond(&self, method: &Method, url: &str, body: String) -> (u16, Value) {
+
+        let path = url.split('?').next().unwrap_or_default();
+        let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+        match (method, segments.as_slice()) {
+
+            (Method::Get, ["days"]) => {
+
+                let days: Vec<Value> = self.solvers
+                .iter()
+                .map(|solver| json!({ "day": solver.day(), "title": solver.title(), "params": solver.params() }))
+                .collect();
+
+                (200, Value::Array(days))
+            }
+
+            (Method::Post, ["day", day]) => self.solve(day, &Part::ALL, body),
+
+            (Method::Post, ["day", day, "part", part]) => match *part {
+                "1" => self.solve(day, &[Part::One], body),
+                "2" => self.solve(day, &[Part::Two], body),
+                _ => (404, error_json(&anyhow!("There is no part {}", part))),
+            },
+
+            (_, ["days"] | ["day", _] | ["day", _, "part", _]) => {
+                (405, error_json(&anyhow!("Method {} is not allowed on {}", method, path)))
+            }
+
+            _ => (404, error_json(&anyhow!("No such endpoint: {}", path))),
+        }
+    }
+
+    fn solve(&self, day: &str, parts: &[Part], body: String) -> (u16, Value) {
+
+        let solver = day.parse::<u8>()
+        .ok()
+        .and_then(|day| self.solvers.iter().find(|solver| solver.day() == day));
+
+        let Some(solver) = solver else {
+            return (404, error_json(&anyhow!("Day {} is not solved yet", day)))
+        };
+
+        // Bodies are not cached: each one is solved once, and may be large
+        let records = solve_records_with(solver.as_ref(), Input::Text(body), parts, None);
+
+        let status = if records.iter().all(|record| record.succeeded()) { 200 } else { 422 };
+
+        let json = match records.as_slice() {
+            [record] => serde_json::to_value(record),
+            _ => serde_json::to_value(&records),
+        };
+
+        match json {
+            Result::Ok(json) => (status, json),
+            Result::Err(error) => (500, error_json(&anyhow!(error))),
+        }
+    }
+}
+
+fn error_json(error: &anyhow::Error) -> Value {
+    json!({ "error": error.chain().map(|cause| cause.to_string()).collect::<Vec<String>>() })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::registry;
+
+    #[test]
+    fn routes() {
+
+        let server = SolverServer::bind("127.0.0.1:0", registry()).unwrap();
+
+        let (status, json) = server.respond(&Method::Post, "/day/4/part/2", "2-4,6-8\n2-8,3-7\n".to_string());
+        assert_eq!((status, &json["answer"]), (200, &json!("1")));
+
+        let (status, json) = server.respond(&Method::Post, "/day/4", "2-4,6-8\n2-8,3-7\n".to_string());
+        assert_eq!((status, json.as_array().map(|records| records.len())), (200, Some(2)));
+
+        let (status, json) = server.respond(&Method::Post, "/day/4/part/1", "2-4,6-x\n".to_string());
+        assert_eq!(status, 422);
+        assert!(json["error"][1].as_str().unwrap().contains("<text>:1:7"));
+
+        assert_eq!(server.respond(&Method::Post, "/day/30/part/1", String::new()).0, 404);
+        assert_eq!(server.respond(&Method::Post, "/day/4/part/3", String::new()).0, 404);
+        assert_eq!(server.respond(&Method::Get, "/day/4/part/1", String::new()).0, 405);
+        assert_eq!(server.respond(&Method::Get, "/days", String::new()).1[5]["params"], json!(["packet_marker", "message_marker"]));
+    }
+}
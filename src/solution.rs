@@ -0,0 +1,278 @@
+// Standard library
+use std::any::{Any, type_name};
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+// External crates
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tracing::{info, info_span};
+
+// Crate modules
+use crate::error::ParseErrors;
+use crate::general::{Input, ParseCache};
+use crate::params::Params;
+use crate::day_1::Day1;
+use crate::day_2::Day2;
+use crate::day_3::Day3;
+use crate::day_4::Day4;
+use crate::day_5::Day5;
+use crate::day_6::Day6;
+
+// A day of the puzzle: parse the input once, then answer each part from the parsed input.
+// The parsed input can be serialized, so it can be cached between runs.
+pub trait Solution {
+    type Parsed: Any + Send + Sync + Serialize + DeserializeOwned;
+    type PartOne: Display;
+    type PartTwo: Display;
+
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    // Names of the parameters the day can be configured with
+    const PARAMS: &'static [&'static str] = &[];
+
+    // Apply parameters, which are all among `PARAMS`. Missing ones keep their default.
+    fn configure(&mut self, _params: &Params) -> Result<()> {
+        Ok(())
+    }
+
+    fn parse(&self, input: Input) -> Result<Self::Parsed>;
+
+    fn part_one(&self, parsed: &Self::Parsed) -> Result<Self::PartOne>;
+
+    fn part_two(&self, parsed: &Self::Parsed) -> Result<Self::PartTwo>;
+
+    // Check the input without solving it. Days parsing line by line override this to
+    // report every bad line at once (as `ParseErrors`); by default it's just parsing.
+    fn validate(&self, input: Input) -> Result<()> {
+        Solution::parse(self, input).map(|_| ())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+// The answer of a part, rendered, along with the type the solver returned
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    value: String,
+    kind: &'static str,
+}
+
+impl Answer {
+
+    pub fn new<T: Display>(value: T) -> Self {
+        Self {
+            value: value.to_string(),
+            kind: short_type_name::<T>(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    // Name of the answer type, without its module path
+    pub fn kind(&self) -> &'static str {
+        self.kind
+    }
+}
+
+fn short_type_name<T>() -> &'static str {
+    let full_name = type_name::<T>();
+    full_name.rsplit("::").next().unwrap_or(full_name)
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+// Type-erased Solution, so every day can live in the same registry. Solvers are shared
+// between threads when days run in parallel.
+pub trait Solver: Send + Sync {
+
+    fn day(&self) -> u8;
+
+    fn title(&self) -> &'static str;
+
+    fn params(&self) -> &'static [&'static str];
+
+    // Apply parameters, failing on any the day doesn't have
+    fn configure(&mut self, params: &Params) -> Result<()>;
+
+    fn parse(&self, input: Input) -> Result<Box<dyn Any>>;
+
+    // Like `parse`, but the same input is only parsed once per cache
+    fn parse_cached(&self, input: Input, cache: &ParseCache) -> Result<Arc<dyn Any + Send + Sync>>;
+
+    fn solve_part(&self, parsed: &dyn Any, part: Part) -> Result<Answer>;
+
+    // Every problem found in the input, without solving it
+    fn validate(&self, input: Input) -> Vec<anyhow::Error>;
+
+    // The day itself, to get at its configuration
+    fn as_any(&self) -> &dyn Any;
+
+    // Parse the input and answer the requested parts, in order
+    fn run(&self, input: Input, parts: &[Part]) -> Result<Vec<Result<Answer>>> {
+
+        let parsed = self.parse(input)?;
+
+        Ok(
+            parts
+            .iter()
+            .map(|part| self.solve_part(parsed.as_ref(), *part))
+            .collect()
+        )
+    }
+}
+
+impl<S: Solution + Send + Sync + 'static> Solver for S {
+
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn title(&self) -> &'static str {
+        S::TITLE
+    }
+
+    fn params(&self) -> &'static [&'static str] {
+        S::PARAMS
+    }
+
+    fn configure(&mut self, params: &Params) -> Result<()> {
+
+        params.check_known(S::DAY, S::PARAMS)?;
+
+        Solution::configure(self, params)
+        .with_context(|| format!("configuring Day {}", S::DAY))
+    }
+
+    fn parse(&self, input: Input) -> Result<Box<dyn Any>> {
+
+        let input_name = input.name();
+
+        let _span = info_span!("parse", day = S::DAY, input = %input_name).entered();
+
+        let parsed = Solution::parse(self, input)
+        .with_context(|| format!("parsing input '{}' of Day {}", input_name, S::DAY))?;
+
+        info!("parsed input");
+
+        Ok(Box::new(parsed))
+    }
+
+    fn parse_cached(&self, input: Input, cache: &ParseCache) -> Result<Arc<dyn Any + Send + Sync>> {
+
+        let input_name = input.name();
+
+        let _span = info_span!("parse", day = S::DAY, input = %input_name).entered();
+
+        let parsed = cache.get_or_parse(&format!("day_{}", S::DAY), input, |input| Solution::parse(self, input))
+        .with_context(|| format!("parsing input '{}' of Day {}", input_name, S::DAY))?;
+
+        info!("parsed input");
+
+        Ok(parsed)
+    }
+
+    fn solve_part(&self, parsed: &dyn Any, part: Part) -> Result<Answer> {
+
+        let Some(parsed) = parsed.downcast_ref::<S::Parsed>() else {
+            return Err(anyhow!("Parsed input given to Day {} is not from Day {}", S::DAY, S::DAY))
+        };
+
+        let _span = info_span!("solve", day = S::DAY, part = part.number()).entered();
+
+        let answer = match part {
+            Part::One => self.part_one(parsed).map(Answer::new),
+            Part::Two => self.part_two(parsed).map(Answer::new),
+        }
+        .with_context(|| format!("solving Day {} Part {}", S::DAY, part))?;
+
+        info!(answer = %answer, "solved");
+
+        Ok(answer)
+    }
+
+    fn validate(&self, input: Input) -> Vec<anyhow::Error> {
+
+        let _span = info_span!("validate", day = S::DAY, input = %input.name()).entered();
+
+        match Solution::validate(self, input) {
+            Result::Ok(()) => Vec::new(),
+            Result::Err(error) => match error.downcast::<ParseErrors>() {
+                Result::Ok(errors) => errors.into_errors(),
+                Result::Err(error) => vec![error],
+            },
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn Solver + '_ {
+
+    // The configured day, as its own type
+    pub fn downcast_ref<S: Solution + 'static>(&self) -> Result<&S> {
+        self.as_any()
+        .downcast_ref::<S>()
+        .ok_or_else(|| anyhow!("Day {} is not solved by {}", self.day(), type_name::<S>()))
+    }
+
+    // Parse an input into the day's own parsed type
+    pub fn parse_as<T: Any>(&self, input: Input) -> Result<T> {
+        self.parse(input)?
+        .downcast::<T>()
+        .map(|parsed| *parsed)
+        .map_err(|_| anyhow!("Day {} doesn't parse into {}", self.day(), type_name::<T>()))
+    }
+}
+
+// Every solved day, in order, with the default parameters
+pub fn registry() -> Vec<Box<dyn Solver>> {
+    vec![
+        Box::new(Day1::default()),
+        Box::new(Day2),
+        Box::new(Day3::default()),
+        Box::new(Day4),
+        Box::new(Day5),
+        Box::new(Day6::default()),
+    ]
+}
+
+// Find the solver of a day in the registry
+pub fn find_solver(day: u8) -> Result<Box<dyn Solver>> {
+    registry()
+    .into_iter()
+    .find(|solver| solver.day() == day)
+    .ok_or_else(|| anyhow!("Day {} is not solved yet", day))
+}
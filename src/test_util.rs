@@ -0,0 +1,38 @@
+// Standard library
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+// A directory for a test's files, unique to the test and the process. It's removed when
+// dropped, so also when an assertion fails.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+
+    pub fn new(name: &str) -> Self {
+
+        let path = std::env::temp_dir().join(format!("advent_22_{}_{}", name, std::process::id()));
+
+        // A leftover of an aborted run would change what the test sees
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+
+        Self { path }
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
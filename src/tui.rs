@@ -0,0 +1,481 @@
+// Standard library
+use std::iter;
+
+// External crates
+use anyhow::Result;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+// Crate modules
+use crate::day_4::AssignmentPair;
+use crate::day_5::{Movement, Stack};
+use crate::day_6::{Day6, ElvenDevice};
+use crate::general::{Input, InputResolver};
+use crate::report::{Record, solve_records};
+use crate::solution::{Part, Solver};
+
+// Widest range bar of the day 4 view, in cells
+const BAR_WIDTH: usize = 60;
+
+// Days with a rendering of their parsed state
+const STATE_DAYS: [u8; 3] = [4, 5, 6];
+
+// Characters shown on each side of a day 6 marker
+const MARKER_CONTEXT: usize = 20;
+
+const KEYS: &str = "↑↓ select  enter re-run  a run all  d details  i input  s state  pgup/pgdn scroll  q quit";
+
+// What the lower pane shows for the selected day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Details,
+    Input,
+    State,
+}
+
+// A dashboard of the days: their status, answers and timings, and for the selected one its
+// records, its input or a rendering of its parsed state
+pub struct App {
+    solvers: Vec<Box<dyn Solver>>,
+    resolver: InputResolver,
+    runs: Vec<Option<Vec<Record>>>,
+    table: TableState,
+    view: View,
+    detail: Vec<Line<'static>>,
+    scroll: u16,
+    quit: bool,
+}
+
+impl App {
+
+    pub fn new(solvers: Vec<Box<dyn Solver>>, resolver: InputResolver) -> Self {
+
+        let runs = solvers.iter().map(|_| None).collect();
+
+        let mut app = Self {
+            solvers,
+            resolver,
+            runs,
+            table: TableState::default().with_selected(0),
+            view: View::Details,
+            detail: Vec::new(),
+            scroll: 0,
+            quit: false,
+        };
+
+        app.refresh_detail();
+        app
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    fn run_day(&mut self, index: usize) {
+
+        let solver = self.solvers[index].as_ref();
+
+        self.runs[index] = Some(solve_records(solver, self.resolver.input(solver.day()), &Part::ALL));
+    }
+
+    pub fn run_all(&mut self) {
+
+        for index in 0..self.solvers.len() {
+            self.run_day(index);
+        }
+
+        self.refresh_detail();
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.select(self.selected().saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select((self.selected() + 1).min(self.solvers.len().saturating_sub(1))),
+            KeyCode::Enter | KeyCode::Char('r') => {
+                self.run_day(self.selected());
+                self.refresh_detail();
+            }
+            KeyCode::Char('a') => self.run_all(),
+            KeyCode::Char('d') => self.show(View::Details),
+            KeyCode::Char('i') => self.show(View::Input),
+            KeyCode::Char('s') => self.show(View::State),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            _ => (),
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.table.select(Some(index));
+        self.refresh_detail();
+    }
+
+    fn show(&mut self, view: View) {
+        self.view = view;
+        self.refresh_detail();
+    }
+
+    // The lower pane is only computed again when the day, the view or a run changes
+    fn refresh_detail(&mut self) {
+
+        self.scroll = 0;
+
+        let Some(solver) = self.solvers.get(self.selected()) else {
+            self.detail = Vec::new();
+            return
+        };
+
+        let day = solver.day();
+
+        self.detail = match self.view {
+            View::Details => record_lines(self.runs[self.selected()].as_deref()),
+            View::Input => match self.resolver.read(day) {
+                Result::Ok(text) => text
+                .lines()
+                .enumerate()
+                .map(|(index, line)| Line::from(format!("{:>5} {}", index + 1, line)))
+                .collect(),
+                Result::Err(error) => error_lines(&error),
+            },
+            View::State if !STATE_DAYS.contains(&day) => vec![Line::from(format!("Day {} has no state view", day))],
+            View::State => match state_lines(solver.as_ref(), self.resolver.input(day)) {
+                Result::Ok(lines) => lines,
+                Result::Err(error) => error_lines(&error),
+            },
+        };
+    }
+
+    pub fn draw(&mut self, frame: &mut Frame) {
+
+        let height = self.solvers.len() as u16 + 3;
+        let [days_area, detail_area, keys_area] = Layout::vertical([
+            Constraint::Length(height),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let rows: Vec<Row> = self.solvers
+        .iter()
+        .zip(&self.runs)
+        .map(|(solver, run)| day_row(solver.as_ref(), run.as_deref()))
+        .collect();
+
+        let table = Table::new(rows, [
+            Constraint::Length(4),
+            Constraint::Length(26),
+            Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(12),
+        ])
+        .header(Row::new(["Day", "Title", "Status", "Part 1", "Part 2", "Time"]).style(Style::new().add_modifier(Modifier::BOLD)))
+        .block(Block::bordered().title(" Days "))
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, days_area, &mut self.table);
+
+        let title = match self.view {
+            View::Details => " Details ",
+            View::Input => " Input ",
+            View::State => " State ",
+        };
+
+        let detail = Paragraph::new(self.detail.clone())
+        .block(Block::bordered().title(title))
+        .scroll((self.scroll, 0));
+
+        frame.render_widget(detail, detail_area);
+        frame.render_widget(Line::from(KEYS).style(Style::new().fg(Color::DarkGray)), keys_area);
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+
+        while !self.quit {
+
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Run every day, then show the dashboard until 'q'. The terminal is restored even if
+// drawing fails.
+pub fn run(solvers: Vec<Box<dyn Solver>>, resolver: InputResolver) -> Result<()> {
+
+    let mut app = App::new(solvers, resolver);
+    app.run_all();
+
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+
+fn day_row(solver: &dyn Solver, run: Option<&[Record]>) -> Row<'static> {
+
+    let Some(records) = run else {
+        return Row::new([solver.day().to_string(), solver.title().to_string(), "-".to_string()])
+    };
+
+    let (status, color) = match records.iter().all(|record| record.succeeded()) {
+        true => ("ok", Color::Green),
+        false => ("failed", Color::Red),
+    };
+
+    let answers = records.iter().map(|record| record.answer.clone().unwrap_or_else(|| "-".to_string()));
+    let wall_duration_ms = records.first().map(|record| record.wall_duration_ms).unwrap_or_default();
+
+    let cells: Vec<Span> = [solver.day().to_string(), solver.title().to_string()]
+    .into_iter()
+    .map(Span::raw)
+    .chain(iter::once(Span::styled(status, Style::new().fg(color))))
+    .chain(answers.map(Span::raw))
+    .chain(iter::once(Span::raw(format!("{:.3} ms", wall_duration_ms))))
+    .collect();
+
+    Row::new(cells)
+}
+
+fn record_lines(run: Option<&[Record]>) -> Vec<Line<'static>> {
+
+    let Some(records) = run else {
+        return vec![Line::from("Not run yet: press enter to run it")]
+    };
+
+    let mut lines = vec![Line::from(format!("Input: {}", records.first().map(|record| record.input.as_str()).unwrap_or_default()))];
+
+    for record in records {
+
+        lines.push(Line::from(""));
+
+        match (&record.answer, &record.error) {
+            (Some(answer), _) => lines.push(Line::from(format!(
+                "Part {}: {} ({}), parsed in {:.3} ms, solved in {:.3} ms",
+                record.part, answer, record.answer_type.clone().unwrap_or_default(), record.parse_duration_ms, record.duration_ms
+            ))),
+            (None, error) => {
+
+                lines.push(Line::styled(format!("Part {} failed:", record.part), Style::new().fg(Color::Red)));
+
+                for message in error.iter().flatten() {
+                    lines.extend(message.lines().map(|line| Line::from(format!("  {}", line))));
+                }
+            }
+        }
+    }
+
+    lines
+}
+
+fn error_lines(error: &anyhow::Error) -> Vec<Line<'static>> {
+    format!("{:#}", error)
+    .lines()
+    .map(|line| Line::styled(line.to_string(), Style::new().fg(Color::Red)))
+    .collect()
+}
+
+// A rendering of a day's parsed input: the ranges of day 4, the stacks of day 5 before and
+// after each crane, and the markers of day 6 in their datastream. The input is parsed by the
+// solver, configured as it is for solving.
+pub fn state_lines(solver: &dyn Solver, input: Input) -> Result<Vec<Line<'static>>> {
+
+    match solver.day() {
+        4 => Ok(pair_lines(&solver.parse_as::<Vec<AssignmentPair>>(input)?)),
+        5 => {
+            let (stack, moves) = solver.parse_as::<(Stack, Vec<Movement>)>(input)?;
+            stack_lines(&stack, &moves)
+        }
+        6 => {
+
+            let day_6 = solver.downcast_ref::<Day6>()?;
+            let markers = [("start-of-packet", day_6.packet_marker()), ("start-of-message", day_6.message_marker())];
+
+            Ok(marker_lines(&solver.parse_as::<ElvenDevice>(input)?, markers))
+        }
+        day => Ok(vec![Line::from(format!("Day {} has no state view", day))]),
+    }
+}
+
+fn pair_lines(pairs: &[AssignmentPair]) -> Vec<Line<'static>> {
+
+    let last_section = pairs
+    .iter()
+    .map(|pair| pair.elf_1().1.max(pair.elf_2().1).max(1) as usize)
+    .max()
+    .unwrap_or(1);
+
+    // Sections per cell, so the widest bar fits
+    let scale = last_section.div_ceil(BAR_WIDTH);
+    let width = last_section.div_ceil(scale);
+
+    let mut lines = vec![Line::from(format!("Sections 1 to {}, {} per cell", last_section, scale))];
+
+    for (index, pair) in pairs.iter().enumerate() {
+
+        let (verdict, color) = match (pair.total_overlap(), pair.simple_overlap()) {
+            (true, _) => ("fully contained", Color::Green),
+            (false, true) => ("overlapping", Color::Yellow),
+            (false, false) => ("apart", Color::DarkGray),
+        };
+
+        let (elf_1, elf_2) = (pair.elf_1(), pair.elf_2());
+
+        lines.push(Line::from(vec![
+            Span::raw(format!("{:>5} ", index + 1)),
+            Span::styled(range_bar(elf_1, scale, width), Style::new().fg(color)),
+            Span::raw(format!(" {}-{}", elf_1.0, elf_1.1)),
+        ]));
+
+        lines.push(Line::from(vec![
+            Span::raw("      "),
+            Span::styled(range_bar(elf_2, scale, width), Style::new().fg(color)),
+            Span::raw(format!(" {}-{}  {}", elf_2.0, elf_2.1, verdict)),
+        ]));
+    }
+
+    lines
+}
+
+// A cell is filled if any of its sections is in the range
+fn range_bar((start, end): (i32, i32), scale: usize, width: usize) -> String {
+    (0..width)
+    .map(|cell| {
+
+        let (first, last) = ((cell * scale + 1) as i64, ((cell + 1) * scale) as i64);
+
+        if first <= end as i64 && last >= start as i64 { '█' } else { '·' }
+    })
+    .collect()
+}
+
+fn stack_lines(stack: &Stack, moves: &[Movement]) -> Result<Vec<Line<'static>>> {
+
+    let mut cratemover_9000 = stack.clone();
+    cratemover_9000.move_boxes(moves)?;
+
+    let mut cratemover_9001 = stack.clone();
+    cratemover_9001.move_boxes_with_cratemover_9001(moves)?;
+
+    let sections = [
+        ("Drawing".to_string(), stack),
+        (format!("After {} moves of the CrateMover 9000", moves.len()), &cratemover_9000),
+        (format!("After {} moves of the CrateMover 9001", moves.len()), &cratemover_9001),
+    ];
+
+    let mut lines = Vec::new();
+
+    for (title, stack) in sections {
+        lines.push(Line::styled(title, Style::new().add_modifier(Modifier::BOLD)));
+        lines.extend(stack.to_string().lines().map(|line| Line::from(line.to_string())));
+        lines.push(Line::from(""));
+    }
+
+    Ok(lines)
+}
+
+fn marker_lines(device: &ElvenDevice, markers: [(&str, usize); 2]) -> Vec<Line<'static>> {
+
+    let datastream = device.datastream();
+    let mut lines = vec![Line::from(format!("{} characters", datastream.len()))];
+
+    for (name, size) in markers {
+
+        lines.push(Line::from(""));
+
+        let Some(end) = device.get_start_of_packet(size, &ElvenDevice::unrepeated_start_of_packet) else {
+            lines.push(Line::from(format!("No {} marker of {} characters", name, size)));
+            continue
+        };
+
+        let start = end - size;
+        let before = start.saturating_sub(MARKER_CONTEXT);
+        let after = (end + MARKER_CONTEXT).min(datastream.len());
+
+        lines.push(Line::from(format!("First {} marker ({} characters) ends at character {}", name, size, end)));
+        lines.push(Line::from(vec![
+            Span::raw(format!("{:>6} ", before + 1)),
+            Span::raw(datastream[before..start].iter().collect::<String>()),
+            Span::styled(datastream[start..end].iter().collect::<String>(), Style::new().fg(Color::Black).bg(Color::Green)),
+            Span::raw(datastream[end..after].iter().collect::<String>()),
+        ]));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use crate::day_4::Day4;
+    use crate::day_5::Day5;
+    use crate::params::Params;
+    use crate::solution::registry;
+
+    fn text(lines: &[Line]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn state_views() {
+
+        let pairs = text(&state_lines(&Day4, include_str!("../tests/fixtures/day_4_example.txt").into()).unwrap());
+        assert_eq!(pairs[0], "Sections 1 to 9, 1 per cell");
+        assert_eq!(pairs[7..9], ["    4 ·███████· 2-8", "      ··█████·· 3-7  fully contained"]);
+
+        let stacks = text(&state_lines(&Day5, include_str!("../tests/fixtures/day_5_example.txt").into()).unwrap());
+        assert!(stacks.ends_with(&["After 4 moves of the CrateMover 9001".to_string(), "        [D]".to_string(), "        [N]".to_string(), "        [Z]".to_string(), "[M] [C] [P]".to_string(), " 1   2   3".to_string(), String::new()]));
+
+        let markers = text(&state_lines(&Day6::default(), include_str!("../tests/fixtures/day_6_example.txt").into()).unwrap());
+        assert_eq!(markers[2..4], ["First start-of-packet marker (4 characters) ends at character 7", "     1 mjqjpqmgbljsphdztnvjfqwrcgs"]);
+
+        // The markers are those of the configured day
+        let mut day_6 = Day6::default();
+        Solver::configure(&mut day_6, &Params::new().with("packet_marker", 5)).unwrap();
+
+        let markers = text(&state_lines(&day_6, include_str!("../tests/fixtures/day_6_example.txt").into()).unwrap());
+        assert_eq!(markers[2], "First start-of-packet marker (5 characters) ends at character 8");
+
+        assert!(state_lines(&Day5, "no drawing".into()).is_err());
+    }
+
+    #[test]
+    fn dashboard() {
+
+        let mut app = App::new(registry(), InputResolver::default());
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+
+        app.handle_key(KeyCode::Down);
+        app.handle_key(KeyCode::Char('s'));
+        assert_eq!(text(&app.detail), ["Day 2 has no state view"]);
+
+        app.handle_key(KeyCode::Char('d'));
+        assert_eq!(text(&app.detail), ["Not run yet: press enter to run it"]);
+
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+
+        let screen: String = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Rock Paper Scissors"));
+        assert!(screen.contains(" Details "));
+
+        app.handle_key(KeyCode::Char('q'));
+        assert!(app.quit);
+    }
+}
@@ -0,0 +1,79 @@
+// Standard library
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+// Polls files for changes of their modification time. A file that doesn't exist yet is
+// watched too, and counts as changed when it appears.
+#[derive(Debug)]
+pub struct FileWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl FileWatcher {
+
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            files: paths
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect()
+        }
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| path.as_path())
+    }
+
+    // Whether any file was modified, created or removed since the last poll
+    pub fn poll(&mut self) -> bool {
+
+        let mut changed = false;
+
+        for (path, last_modified) in self.files.iter_mut() {
+
+            let modified = modified(path);
+
+            if modified != *last_modified {
+                *last_modified = modified;
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::time::Duration;
+
+    use crate::test_util::TempDir;
+
+    #[test]
+    fn poll_sees_modifications_and_new_files() {
+
+        let dir = TempDir::new("watch");
+        let input = dir.join("day_5.txt");
+
+        let mut watcher = FileWatcher::new(vec![input.clone()]);
+        assert!(!watcher.poll());
+
+        fs::write(&input, "move 1 from 2 to 1\n").unwrap();
+        assert!(watcher.poll());
+        assert!(!watcher.poll());
+
+        let file = File::options().write(true).open(&input).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+        assert!(watcher.poll());
+    }
+}
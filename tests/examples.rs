@@ -0,0 +1,207 @@
+// Worked examples from each day's puzzle text, solved through the registry and through
+// the standalone entry points.
+
+// Standard library
+use std::io::Cursor;
+use std::path::Path;
+
+// External crates
+use anyhow::Result;
+
+// Crate modules
+use advent_22::error::AdventError;
+use advent_22::general::Input;
+use advent_22::params::Params;
+use advent_22::{Part, Solver, find_solver};
+use advent_22::day_1::*;
+use advent_22::day_2::*;
+use advent_22::day_3::*;
+use advent_22::day_4::*;
+use advent_22::day_5::*;
+use advent_22::day_6::*;
+
+const DAY_1_EXAMPLE: &str = include_str!("fixtures/day_1_example.txt");
+const DAY_2_EXAMPLE: &str = include_str!("fixtures/day_2_example.txt");
+const DAY_3_EXAMPLE: &str = include_str!("fixtures/day_3_example.txt");
+const DAY_4_EXAMPLE: &str = include_str!("fixtures/day_4_example.txt");
+const DAY_5_EXAMPLE: &str = include_str!("fixtures/day_5_example.txt");
+const DAY_6_EXAMPLE: &str = include_str!("fixtures/day_6_example.txt");
+
+// Solve both parts of a day with its registered solver
+fn solve_both(day: u8, input: &str) -> Result<(String, String)> {
+
+    let solver: Box<dyn Solver> = find_solver(day)?;
+
+    let mut answers = solver.run(Input::from(input), &Part::ALL)?.into_iter();
+
+    let part_one = answers.next().expect("part one answer")?;
+    let part_two = answers.next().expect("part two answer")?;
+
+    Ok((part_one.to_string(), part_two.to_string()))
+}
+
+#[test]
+fn day_1_example() -> Result<()> {
+
+    assert_eq!(solve_both(1, DAY_1_EXAMPLE)?, ("24000".to_string(), "45000".to_string()));
+
+    assert_eq!(get_elf_calories(DAY_1_EXAMPLE)?, 24000);
+    assert_eq!(get_top_n_elf_calories(DAY_1_EXAMPLE, 3)?, 45000);
+
+    Ok(())
+}
+
+#[test]
+fn day_2_example() -> Result<()> {
+
+    assert_eq!(solve_both(2, DAY_2_EXAMPLE)?, ("15".to_string(), "12".to_string()));
+
+    assert_eq!(simulate_input_strategy_results(DAY_2_EXAMPLE)?, 15);
+    assert_eq!(simulate_input_strategy_results_2(DAY_2_EXAMPLE)?, 12);
+
+    Ok(())
+}
+
+#[test]
+fn day_3_example() -> Result<()> {
+
+    assert_eq!(solve_both(3, DAY_3_EXAMPLE)?, ("157".to_string(), "70".to_string()));
+
+    assert_eq!(get_total_priority(DAY_3_EXAMPLE)?, 157);
+    assert_eq!(get_badges_priority(DAY_3_EXAMPLE)?, 70);
+
+    Ok(())
+}
+
+#[test]
+fn day_4_example() -> Result<()> {
+
+    assert_eq!(solve_both(4, DAY_4_EXAMPLE)?, ("2".to_string(), "4".to_string()));
+
+    assert_eq!(get_complete_overlaps(DAY_4_EXAMPLE)?, 2);
+    assert_eq!(get_partial_overlaps(DAY_4_EXAMPLE)?, 4);
+
+    Ok(())
+}
+
+#[test]
+fn day_5_example() -> Result<()> {
+
+    assert_eq!(solve_both(5, DAY_5_EXAMPLE)?, ("CMZ".to_string(), "MCD".to_string()));
+
+    assert_eq!(get_stack_tops(DAY_5_EXAMPLE)?, "CMZ");
+    assert_eq!(get_stack_tops_with_cratemover_9001(DAY_5_EXAMPLE)?, "MCD");
+
+    Ok(())
+}
+
+#[test]
+fn day_6_example() -> Result<()> {
+
+    assert_eq!(solve_both(6, DAY_6_EXAMPLE)?, ("7".to_string(), "19".to_string()));
+
+    assert_eq!(get_start_of_packet_4_unrepeated(DAY_6_EXAMPLE)?, Some(7));
+    assert_eq!(get_start_of_packet_14_unrepeated(DAY_6_EXAMPLE)?, Some(19));
+
+    Ok(())
+}
+
+#[test]
+fn day_6_more_examples() -> Result<()> {
+
+    // Datastream, start-of-packet, start-of-message
+    let examples = [
+        ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+        ("nppdvjthqldpwncqszvftbrmjlhg", 6, 23),
+        ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+        ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+    ];
+
+    for (datastream, start_of_packet, start_of_message) in examples {
+        assert_eq!(get_start_of_packet_4_unrepeated(datastream)?, Some(start_of_packet), "{}", datastream);
+        assert_eq!(get_start_of_packet_14_unrepeated(datastream)?, Some(start_of_message), "{}", datastream);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn configured_days() -> Result<()> {
+
+    let mut solver = find_solver(1)?;
+    solver.configure(&Params::new().with("top", 2))?;
+
+    let answers = solver.run(Input::from(DAY_1_EXAMPLE), &[Part::Two])?;
+    assert_eq!(answers[0].as_ref().unwrap().to_string(), "35000");
+
+    let mut solver = find_solver(6)?;
+    solver.configure(&Params::new().with("packet_marker", 3).with("message_marker", 5))?;
+
+    let answers: Vec<String> = solver
+    .run(Input::from(DAY_6_EXAMPLE), &Part::ALL)?
+    .into_iter()
+    .map(|answer| answer.unwrap().to_string())
+    .collect();
+
+    assert_eq!(answers, vec!["3", "8"]);
+
+    // Unknown parameters and days without parameters are errors
+    assert!(find_solver(6)?.configure(&Params::new().with("marker", 3)).is_err());
+    assert!(find_solver(4)?.configure(&Params::new().with("top", 3)).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn entry_points_accept_paths_and_readers() -> Result<()> {
+
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/day_5_example.txt");
+
+    assert_eq!(get_stack_tops(fixture.as_path())?, "CMZ");
+    assert_eq!(get_stack_tops(Input::reader(Cursor::new(DAY_5_EXAMPLE)))?, "CMZ");
+    assert_eq!(get_stack_tops(Input::from_arg(fixture.to_str().unwrap()))?, "CMZ");
+
+    assert!(get_stack_tops(Path::new("tests/fixtures/missing.txt")).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn parse_errors_point_at_line_and_column() -> Result<()> {
+
+    let solver = find_solver(4)?;
+
+    let error = solver.parse(Input::from("2-4,6-8\n2-3,4-x\n")).expect_err("parse error");
+
+    let Some(AdventError::Parse { span: Some(span), .. }) = error.downcast_ref::<AdventError>() else {
+        panic!("expected a parse error with a span, got {:?}", error);
+    };
+
+    assert_eq!((span.input(), span.line(), span.column()), (Some("<text>"), Some(2), 7));
+    assert_eq!(span.snippet(), "2-3,4-x");
+
+    Ok(())
+}
+
+#[test]
+fn validation_reports_every_bad_line() -> Result<()> {
+
+    let input = "2-4,6-8\n2-x,4-5\n1-2\n3-4,5-6\n";
+
+    let solver = find_solver(4)?;
+
+    let errors = solver.validate(Input::from(input));
+
+    let lines: Vec<Option<usize>> = errors
+    .iter()
+    .map(|error| error.downcast_ref::<AdventError>().and_then(|error| error.span()).and_then(|span| span.line()))
+    .collect();
+
+    assert_eq!(lines, vec![Some(2), Some(3)]);
+
+    // Solving still stops at the first bad line
+    assert!(solver.parse(Input::from(input)).is_err());
+    assert!(solver.validate(Input::from(DAY_4_EXAMPLE)).is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// Property tests of the puzzle logic over random inputs, checked against invariants and
+// naive oracles.
+
+// Standard library
+use std::collections::HashSet;
+
+// External crates
+use proptest::prelude::*;
+
+// Crate modules
+use advent_22::day_2::*;
+use advent_22::day_4::*;
+use advent_22::day_6::*;
+
+// A range of sections, with its start before its end
+fn section_range() -> impl Strategy<Value = (i32, i32)> {
+    (1..100i32, 0..100i32).prop_map(|(start, length)| (start, start + length))
+}
+
+fn rps_move() -> impl Strategy<Value = RPSMove> {
+    prop_oneof![Just(RPSMove::Rock), Just(RPSMove::Paper), Just(RPSMove::Scissors)]
+}
+
+fn strategy_move() -> impl Strategy<Value = StrategyMove> {
+    prop_oneof![Just(StrategyMove::Win), Just(StrategyMove::Lose), Just(StrategyMove::Draw)]
+}
+
+fn sections((start, end): (i32, i32)) -> HashSet<i32> {
+    (start..=end).collect()
+}
+
+// Rock, Paper and Scissors as 0, 1 and 2: each shape beats the one before it
+fn shape_number(shape: RPSMove) -> i32 {
+    match shape {
+        RPSMove::Rock => 0,
+        RPSMove::Paper => 1,
+        RPSMove::Scissors => 2,
+    }
+}
+
+fn all_distinct(window: &[char]) -> bool {
+    window.iter().collect::<HashSet<_>>().len() == window.len()
+}
+
+proptest! {
+
+    #[test]
+    fn partial_overlap_is_symmetric(range_1 in section_range(), range_2 in section_range()) {
+        prop_assert_eq!(partially_contains(range_1, range_2), partially_contains(range_2, range_1));
+    }
+
+    #[test]
+    fn full_containment_implies_partial_overlap(range_1 in section_range(), range_2 in section_range()) {
+        if fully_contains(range_1, range_2) {
+            prop_assert!(partially_contains(range_1, range_2));
+        }
+    }
+
+    #[test]
+    fn overlaps_match_section_sets(range_1 in section_range(), range_2 in section_range()) {
+
+        let (sections_1, sections_2) = (sections(range_1), sections(range_2));
+
+        prop_assert_eq!(
+            fully_contains(range_1, range_2),
+            sections_1.is_subset(&sections_2) || sections_2.is_subset(&sections_1)
+        );
+        prop_assert_eq!(partially_contains(range_1, range_2), !sections_1.is_disjoint(&sections_2));
+    }
+
+    #[test]
+    fn play_matches_modular_oracle(self_move in rps_move(), opponent_move in rps_move()) {
+
+        let shape_score = shape_number(self_move) + 1;
+
+        let expected = match (shape_number(self_move) - shape_number(opponent_move)).rem_euclid(3) {
+            0 => RPSResult::Draw(shape_score + 3),
+            1 => RPSResult::SelfWin(shape_score + 6),
+            _ => RPSResult::SelfLoss(shape_score),
+        };
+
+        prop_assert_eq!(self_move.play(opponent_move), expected);
+    }
+
+    #[test]
+    fn strategy_yields_requested_result(strategy in strategy_move(), opponent_move in rps_move()) {
+
+        let result = strategy.move_with_strategy(opponent_move).play(opponent_move);
+
+        let yielded = match result {
+            RPSResult::SelfWin(_) => StrategyMove::Win,
+            RPSResult::SelfLoss(_) => StrategyMove::Lose,
+            RPSResult::Draw(_) => StrategyMove::Draw,
+        };
+
+        prop_assert_eq!(yielded, strategy);
+    }
+
+    #[test]
+    fn unrepeated_matches_set_oracle(window in proptest::collection::vec(proptest::char::range('a', 'h'), 0..16)) {
+        prop_assert_eq!(ElvenDevice::unrepeated_start_of_packet(&window), all_distinct(&window));
+    }
+
+    #[test]
+    fn marker_is_the_first_distinct_window(datastream in "[a-p]{0,60}", marker_size in 1..16usize) {
+
+        let device = ElvenDevice::new(&datastream);
+        let chars = device.datastream();
+
+        match device.get_start_of_packet(marker_size, &ElvenDevice::unrepeated_start_of_packet) {
+            Some(end) => {
+
+                prop_assert!(end >= marker_size && end <= chars.len());
+                prop_assert!(all_distinct(&chars[end - marker_size..end]));
+
+                for earlier_end in marker_size..end {
+                    prop_assert!(!all_distinct(&chars[earlier_end - marker_size..earlier_end]));
+                }
+            }
+            None => {
+                prop_assert!(chars.windows(marker_size).all(|window| !all_distinct(window)));
+            }
+        }
+    }
+}
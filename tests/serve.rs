@@ -0,0 +1,60 @@
+#![cfg(feature = "serve")]
+
+// Standard library
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::sync::Arc;
+use std::thread;
+
+// Crate modules
+use advent_22::registry;
+use advent_22::serve::SolverServer;
+
+const DAY_5_EXAMPLE: &str = include_str!("fixtures/day_5_example.txt");
+
+// A bare HTTP/1.1 request, returning the status line and the body
+fn request(address: SocketAddr, method: &str, path: &str, body: &str) -> (String, String) {
+
+    let mut stream = TcpStream::connect(address).unwrap();
+
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method, path, address, body.len(), body
+    ).unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+
+    (head.lines().next().unwrap().to_string(), body.to_string())
+}
+
+#[test]
+fn serves_answers_over_http() {
+
+    let server = Arc::new(SolverServer::bind("127.0.0.1:0", registry()).unwrap());
+    let address = server.local_addr().unwrap();
+
+    let worker = thread::spawn({
+        let server = server.clone();
+        move || server.run()
+    });
+
+    let (status, body) = request(address, "POST", "/day/5/part/2", DAY_5_EXAMPLE);
+    let record: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(status, "HTTP/1.1 200 OK");
+    assert_eq!((&record["day"], &record["part"], &record["answer"]), (&5.into(), &2.into(), &"MCD".into()));
+
+    let (status, body) = request(address, "POST", "/day/5/part/1", "not a drawing\n");
+    let record: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert!(status.starts_with("HTTP/1.1 422"));
+    assert!(record["answer"].is_null() && record["error"].is_array());
+
+    let (status, _) = request(address, "POST", "/day/9/part/1", "");
+    assert!(status.starts_with("HTTP/1.1 404"));
+
+    server.stop();
+    worker.join().unwrap();
+}